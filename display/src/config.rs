@@ -22,6 +22,14 @@ pub struct Config {
     pub gamepad: bool,
     #[derivative(Default(value = "OptimisationConfig::default()"))]
     pub optimisation: OptimisationConfig, // threading: ThreadingConfig?
+    #[derivative(Default(value = "None"))]
+    pub game_seed: Option<u64>, // None -> random level
+    #[derivative(Default(value = "game::GameConfig::default()"))]
+    pub game: game::GameConfig,
+    #[derivative(Default(value = "None"))]
+    pub replay: Option<String>, // Plays back this replay file instead of the brain
+    #[derivative(Default(value = "None"))]
+    pub track: Option<String>, // Plays this track file (.ron or .json) instead of a generated level
 }
 
 #[derive(derivative::Derivative, serde::Deserialize, Debug, Clone)]
@@ -17,8 +17,9 @@ struct Display {
     frame_stats: utils::framestats::FrameStats,
     gui_menu: gui::Gui,
     global_ui: ui::UserInterface,
-    game: game::Game,
+    env: game::Env<[f32; ring::AGENT_IN]>,
     nn: neat::NeuralNetwork<{ ring::AGENT_IN }, { ring::AGENT_OUT }>,
+    replay: Option<game::replay::Replay>,
     threadpool: stp::ThreadPool,
 }
 
@@ -95,6 +96,33 @@ impl Display {
             "",
         );
 
+        if let Some(path) = cfg.track.as_ref() {
+            match game::track::Track::load(path) {
+                Ok(track) => cfg.game.track = Some(track),
+                Err(e) => {
+                    error!("Could not load track {path}: {e}\n\tFalling back to the generator")
+                }
+            }
+        }
+
+        let replay = cfg
+            .replay
+            .as_ref()
+            .and_then(|path| match game::replay::Replay::load(path) {
+                Ok(replay) => Some(replay),
+                Err(e) => {
+                    error!("Could not load replay {path}: {e}\n\tFalling back to the brain");
+                    None
+                }
+            });
+
+        let game = match (&replay, cfg.game_seed) {
+            (Some(replay), _) => replay.game(),
+            (None, Some(seed)) => game::Game::with_seed(cfg.game.clone(), seed),
+            (None, None) => game::Game::new(cfg.game.clone()),
+        };
+        debug!("Playing game with seed: {}", game.seed());
+
         Ok(
             Self {
                 cfg,
@@ -103,17 +131,40 @@ impl Display {
                 frame_stats: utils::framestats::FrameStats::new(),
                 gui_menu,
                 global_ui,
-                game: game::Game::new(),
-                nn: serde_json::from_str::<
-                    neat::NeuralNetwork<{ ring::AGENT_IN }, { ring::AGENT_OUT }>,
-                >(include_str!("./nnt.json"))
-                .unwrap(),
+                env: game::Env::new(game, |game| ring::generate_inputs(game, 0)),
+                nn: load_brain(),
+                replay,
                 threadpool,
             },
         )
     }
 }
 
+// The bundled network was trained with fewer actions than there are now (no Shoot),
+// the missing outputs are added with the lowest bias so they never get picked
+fn load_brain() -> neat::NeuralNetwork<{ ring::AGENT_IN }, { ring::AGENT_OUT }> {
+    let mut network: serde_json::Value = serde_json::from_str(include_str!("./nnt.json")).unwrap();
+
+    let outputs = network["output_layer"].as_array_mut().unwrap();
+    if outputs.len() < ring::AGENT_OUT {
+        warn!(
+            "The bundled network has {} outputs out of {}, the others are never used",
+            outputs.len(),
+            ring::AGENT_OUT
+        );
+    }
+    while outputs.len() < ring::AGENT_OUT {
+        outputs.push(serde_json::json!({
+            "input_count": 0,
+            "outputs": [],
+            "bias": f32::MIN,
+            "activation_fn": "linear_activation",
+        }));
+    }
+
+    serde_json::from_value(network).unwrap()
+}
+
 impl ggez::event::EventHandler for Display {
     fn update(&mut self, ctx: &mut ggez::Context) -> ggez::GameResult {
         self.frame_stats.end_frame();
@@ -122,8 +173,6 @@ impl ggez::event::EventHandler for Display {
 
         let dt: f64 = ctx.time.delta().as_secs_f64();
 
-        self.game.update(dt);
-
         // {
         //     if input::pressed(ctx, input::Input::KeyboardQ) {
         //         self.game.player_move_left()
@@ -131,24 +180,33 @@ impl ggez::event::EventHandler for Display {
         //         self.game.player_move_right()
         //     }
         // }
-        {
-            let output = self.nn.predict(ring::generate_inputs(&self.game));
-
-            println!("output: {output:?}");
-            match neat::MaxIndex::max_index(output.iter()).unwrap() {
-                0 => (), // No action
-                1 => self.game.player_move_left(),
-                2 => self.game.player_move_right(),
-                _ => (),
-            }
+        // Same loop as the training: one decision per fixed tick
+        for _ in 0..self.env.accumulate(dt) {
+            let action = match &self.replay {
+                Some(replay) => replay.action_at(self.env.game().ticks),
+                None => {
+                    let output = self.nn.predict(self.env.observe());
+
+                    println!("output: {output:?}");
+                    game::Action::from_output(&output)
+                }
+            };
+
+            self.env.step(action);
         }
 
         self.gui_menu.update(ctx, &mut self.cfg)?;
 
+        // Modes with a clock show the time left next to the score
+        let info = self.env.info();
+        let score = match self.env.game().config.mode.time_left(info.ticks) {
+            Some(left) => format!("{} ({left:.1}s)", info.score),
+            None => format!("{}", info.score),
+        };
         self.global_ui
             .get_element("Score")
             .inner_mut::<ui::element::Text>()
-            .replace_bits(vec![format!("{}", self.game.score()).into()]);
+            .replace_bits(vec![score.into()]);
 
         // self.global_ui.update(ctx);
 
@@ -186,27 +244,111 @@ impl ggez::event::EventHandler for Display {
 
         self.global_ui.draw(ctx, render_request)?;
 
-        for platform in self.game.platforms.iter() {
+        let game = self.env.game();
+        // Seen from the first player's camera, with what it broke and killed
+        let scroll = game.players[0].camera.scroll;
+        let level = &game.players[0].level;
+
+        for platform in game
+            .platforms
+            .iter()
+            .filter(|platform| !level.is_removed(platform))
+        {
+            use assets::texture::TextureId;
+            use game::platform::PlatformType;
+
             render_request.add(
-                assets::texture::TextureId::GreenPlatform,
+                match platform.kind {
+                    PlatformType::Green => TextureId::GreenPlatform,
+                    PlatformType::Blue => TextureId::BluePlatform,
+                    PlatformType::White => TextureId::WhitePlatform,
+                    PlatformType::Red => TextureId::RedPlatform,
+                    PlatformType::Cracked => match level.crack_stage(platform) {
+                        0 => TextureId::CrackedPlatform0,
+                        1 => TextureId::CrackedPlatform1,
+                        2 => TextureId::CrackedPlatform2,
+                        _ => TextureId::CrackedPlatform3,
+                    },
+                },
                 render::DrawParam::new()
-                    .pos(platform.rect.center() - math::Vec2::new(0., self.game.scroll as f64))
+                    .pos(
+                        platform.rect.center() - math::Vec2::new(0., scroll - level.fall(platform)),
+                    )
                     .size(platform.rect.size()),
                 render::Layer::Game,
             );
         }
 
-        render_request.add(
-            match self.game.player.direction() {
-                0 | 1 => assets::texture::TextureId::DoodleRight,
-                -1 => assets::texture::TextureId::DoodleLeft,
-                _ => unreachable!(),
-            },
-            render::DrawParam::new()
-                .pos(self.game.player.rect.center() - math::Vec2::new(0., self.game.scroll as f64))
-                .size(self.game.player.rect.size()),
-            render::Layer::Game,
-        );
+        for enemy in game.enemies.iter().filter(|enemy| level.is_alive(enemy)) {
+            use assets::texture::TextureId;
+            use game::enemy::EnemyType;
+
+            render_request.add(
+                match enemy.kind {
+                    EnemyType::Static => TextureId::Mob0,
+                    EnemyType::Hovering => TextureId::Mob1,
+                    EnemyType::Walker if enemy.facing_right() => TextureId::Mob2Right,
+                    EnemyType::Walker => TextureId::Mob2Left,
+                },
+                render::DrawParam::new()
+                    .pos(enemy.rect.center() - math::Vec2::new(0., scroll))
+                    .size(enemy.rect.size()),
+                render::Layer::Game,
+            );
+        }
+
+        for power_up in game
+            .power_ups
+            .iter()
+            .filter(|power_up| level.is_available(power_up))
+        {
+            use game::power_up::PowerUpType;
+
+            render_request.add(
+                assets::texture::TextureId::Dynamic(assets::texture::id::DynamicTextureParams {
+                    size: 1,
+                    color: match power_up.kind {
+                        PowerUpType::Spring => render::Color::from_rgba(180, 180, 180, 255),
+                        PowerUpType::Trampoline => render::Color::from_rgba(40, 90, 220, 255),
+                        PowerUpType::Propeller => render::Color::from_rgba(230, 60, 60, 255),
+                        PowerUpType::Jetpack => render::Color::from_rgba(120, 120, 120, 255),
+                    },
+                }),
+                render::DrawParam::new()
+                    .pos(power_up.rect.center() - math::Vec2::new(0., scroll))
+                    .size(power_up.rect.size()),
+                render::Layer::Game,
+            );
+        }
+
+        for player in game.players.iter() {
+            for projectile in player.projectiles.iter() {
+                render_request.add(
+                    assets::texture::TextureId::Dynamic(
+                        assets::texture::id::DynamicTextureParams {
+                            size: 1,
+                            color: render::Color::from_rgba(255, 220, 0, 255),
+                        },
+                    ),
+                    render::DrawParam::new()
+                        .pos(projectile.rect.center() - math::Vec2::new(0., scroll))
+                        .size(projectile.rect.size()),
+                    render::Layer::Game,
+                );
+            }
+
+            render_request.add(
+                if player.facing_right() {
+                    assets::texture::TextureId::DoodleRight
+                } else {
+                    assets::texture::TextureId::DoodleLeft
+                },
+                render::DrawParam::new()
+                    .pos(player.rect.center() - math::Vec2::new(0., scroll))
+                    .size(player.rect.size()),
+                render::Layer::Game,
+            );
+        }
 
         let render_log = self.renderer.run(
             ctx,
@@ -0,0 +1,109 @@
+// What an agent can do for one tick
+// The order is the order of the network outputs, keep them in sync when adding one
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum Action {
+    #[default]
+    None,
+    Left,
+    Right,
+    Shoot,
+    // Analog control in [-1, 1], for agents with a continuous output
+    // Not part of the network outputs of Action::from_output
+    Steer(f32),
+}
+
+impl Action {
+    pub const ALL: [Self; 4] = [Self::None, Self::Left, Self::Right, Self::Shoot];
+    pub const COUNT: usize = Self::ALL.len();
+
+    // The action with the highest network output, the first one on ties
+    // NaN outputs are skipped, None if there's nothing left
+    pub fn from_output(output: &[f32]) -> Self {
+        output
+            .iter()
+            .zip(Self::ALL)
+            .filter(|(value, _)| !value.is_nan())
+            .reduce(|best, current| if current.0 > best.0 { current } else { best })
+            .map(|(_, action)| action)
+            .unwrap_or_default()
+    }
+
+    // -1 for left, 1 for right
+    pub fn direction(&self) -> i8 {
+        let steering = self.steering();
+
+        if steering < 0. {
+            -1
+        } else if steering > 0. {
+            1
+        } else {
+            0
+        }
+    }
+
+    // Horizontal input in [-1, 1]
+    pub fn steering(&self) -> f64 {
+        match self {
+            Self::Left => -1.,
+            Self::Right => 1.,
+            Self::Steer(value) if value.is_finite() => (*value as f64).clamp(-1., 1.),
+            _ => 0.,
+        }
+    }
+
+    pub fn is_shooting(&self) -> bool {
+        matches!(self, Self::Shoot)
+    }
+}
+
+#[cfg(test)]
+mod actions {
+    use super::*;
+
+    #[test]
+    fn highest() {
+        assert_eq!(Action::from_output(&[0.1, 0.2, 0.9, 0.3]), Action::Right);
+        assert_eq!(Action::from_output(&[-1., -3., -2., -0.5]), Action::Shoot);
+    }
+
+    #[test]
+    fn ties() {
+        assert_eq!(Action::from_output(&[0.5, 0.5, 0.5, 0.5]), Action::None);
+        assert_eq!(Action::from_output(&[0., 1., 1., 0.]), Action::Left);
+    }
+
+    #[test]
+    fn nan() {
+        assert_eq!(
+            Action::from_output(&[f32::NAN, 0.1, 0.3, 0.2]),
+            Action::Right
+        );
+        assert_eq!(
+            Action::from_output(&[0.1, -f32::NAN, 0.3, 0.2]),
+            Action::Right
+        );
+        assert_eq!(Action::from_output(&[f32::NAN; 4]), Action::None);
+    }
+
+    #[test]
+    fn steering() {
+        assert_eq!(Action::Left.steering(), -1.);
+        assert_eq!(Action::Right.steering(), 1.);
+        assert_eq!(Action::Shoot.steering(), 0.);
+        assert_eq!(Action::Steer(-0.5).steering(), -0.5);
+        // Clamped
+        assert_eq!(Action::Steer(3.).steering(), 1.);
+        assert_eq!(Action::Steer(-3.).steering(), -1.);
+        // Ignored
+        assert_eq!(Action::Steer(f32::NAN).steering(), 0.);
+        assert_eq!(Action::Steer(f32::INFINITY).steering(), 0.);
+
+        assert_eq!(Action::Steer(0.2).direction(), 1);
+        assert_eq!(Action::Steer(0.).direction(), 0);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(Action::from_output(&[]), Action::None);
+    }
+}
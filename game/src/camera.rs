@@ -0,0 +1,141 @@
+// Each player's view of the level, the loss condition and the observations are based on it
+use crate::GameConfig;
+
+// How the camera follows the player, it never goes down
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
+pub enum FollowPolicy {
+    // Keeps the player in the middle of the screen, like the original game
+    #[default]
+    Snap,
+    // Catches up with the Snap position, a higher rate catches up faster
+    Lerp {
+        rate: f64,
+    },
+    // Only moves when the player goes above that fraction of the screen height
+    DeadZone {
+        top: f64,
+    },
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct Camera {
+    // y of the top of the screen, negative when going up
+    pub scroll: f64,
+    // World height of y = 0, moved when the game is rebased, see Game::rebase
+    pub origin: f64,
+}
+
+impl Camera {
+    pub fn update(&mut self, config: &GameConfig, target: maths::Point, dt: f64) {
+        let new_scroll = match config.camera {
+            FollowPolicy::Snap => target.y - config.height / 2.,
+            FollowPolicy::Lerp { rate } => {
+                let snap = target.y - config.height / 2.;
+                // Same smoothing whatever the tick rate is
+                self.scroll + (snap - self.scroll) * (1. - (-rate * dt).exp())
+            }
+            FollowPolicy::DeadZone { top } => target.y - config.height * top.clamp(0., 1.),
+        };
+
+        if new_scroll < self.scroll {
+            self.scroll = new_scroll;
+        }
+    }
+
+    // Height of the top of the screen since the start, it isn't affected by rebasing
+    pub fn height(&self) -> f64 {
+        self.origin - self.scroll
+    }
+
+    pub(crate) fn rebase(&mut self, shift: f64) {
+        self.scroll += shift;
+        self.origin += shift;
+    }
+
+    pub fn top(&self) -> f64 {
+        self.scroll
+    }
+
+    pub fn bottom(&self, config: &GameConfig) -> f64 {
+        self.scroll + config.height
+    }
+
+    // Whether the point went below the screen
+    pub fn is_below(&self, config: &GameConfig, point: maths::Point) -> bool {
+        point.y > self.bottom(config)
+    }
+
+    // Position relative to the screen, (0, 0) is the top left and (1, 1) the bottom right
+    pub fn normalize(&self, config: &GameConfig, point: maths::Point) -> maths::Vec2 {
+        maths::Vec2::new(
+            point.x / config.width,
+            (point.y - self.scroll) / config.height,
+        )
+    }
+}
+
+#[cfg(test)]
+mod cameras {
+    use super::*;
+
+    fn config(camera: FollowPolicy) -> GameConfig {
+        GameConfig {
+            camera,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn only_goes_up() {
+        let config = config(FollowPolicy::Snap);
+        let mut camera = Camera::default();
+
+        camera.update(&config, maths::Point::new(0., 400.5), 0.05);
+        assert_eq!(camera.scroll, -79.5);
+
+        camera.update(&config, maths::Point::new(0., 900.), 0.05);
+        assert_eq!(camera.scroll, -79.5);
+    }
+
+    #[test]
+    fn dead_zone() {
+        let config = config(FollowPolicy::DeadZone { top: 0.25 });
+        let mut camera = Camera::default();
+
+        camera.update(&config, maths::Point::new(0., 300.), 0.05);
+        assert_eq!(camera.scroll, 0.);
+
+        camera.update(&config, maths::Point::new(0., 200.), 0.05);
+        assert_eq!(camera.scroll, -40.);
+    }
+
+    #[test]
+    fn lerp_catches_up() {
+        let config = config(FollowPolicy::Lerp { rate: 10. });
+        let mut camera = Camera::default();
+
+        camera.update(&config, maths::Point::new(0., 380.), 0.05);
+        assert!(camera.scroll < 0. && camera.scroll > -100.);
+
+        for _ in 0..100 {
+            camera.update(&config, maths::Point::new(0., 380.), 0.05);
+        }
+        assert!((camera.scroll + 100.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn loss_and_normalization() {
+        let config = GameConfig::default();
+        let camera = Camera {
+            scroll: -480.,
+            origin: 0.,
+        };
+
+        assert!(!camera.is_below(&config, maths::Point::new(0., 479.)));
+        assert!(camera.is_below(&config, maths::Point::new(0., 481.)));
+
+        let position = camera.normalize(&config, maths::Point::new(270., 0.));
+        assert_eq!(position.x, 0.5);
+        assert_eq!(position.y, 0.5);
+    }
+}
@@ -0,0 +1,84 @@
+// Everything that can be tuned without recompiling, the default values are the original game
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct GameConfig {
+    pub width: f64,
+    pub height: f64,
+
+    pub gravity: f64,
+    // Upward speed given by a bounce
+    pub jump_height: f64,
+    // Horizontal speed, the top speed with an inertial movement
+    pub speed: f64,
+    pub movement: crate::player::MovementModel,
+    pub player_size: f64,
+    pub camera: crate::camera::FollowPolicy,
+
+    // Number of platforms at the start of the game
+    pub platform_limit: u32,
+    pub platform_width: f64,
+    pub platform_height: f64,
+
+    // Minimum time between two shots, in seconds
+    pub shoot_cooldown: f64,
+
+    pub difficulty: crate::difficulty::DifficultyProfile,
+
+    pub mode: crate::mode::GameMode,
+    pub termination: crate::termination::TerminationConfig,
+
+    // Plays that level instead of a generated one, see Track::load
+    pub track: Option<crate::track::Track>,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            width: 540.,
+            height: 960.,
+            gravity: 400.,
+            jump_height: 575.,
+            speed: 400.,
+            movement: crate::player::MovementModel::Digital,
+            player_size: 30.,
+            camera: crate::camera::FollowPolicy::Snap,
+            platform_limit: 5,
+            platform_width: 70.,
+            platform_height: 20.,
+            shoot_cooldown: 0.3,
+            difficulty: crate::difficulty::DifficultyProfile::default(),
+            mode: crate::mode::GameMode::Endless,
+            termination: crate::termination::TerminationConfig::default(),
+            track: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod configs {
+    use super::*;
+
+    #[test]
+    fn partial() {
+        let config: GameConfig =
+            serde_json::from_str(r#"{ "gravity": 500, "termination": { "max_ticks": 100 } }"#)
+                .unwrap();
+        let default = GameConfig::default();
+
+        assert_eq!(config.gravity, 500.);
+        assert_eq!(config.termination.max_ticks, Some(100));
+        // Everything else keeps its default value
+        assert_eq!(config.jump_height, default.jump_height);
+        assert_eq!(config.width, default.width);
+        assert_eq!(config.platform_limit, default.platform_limit);
+        assert_eq!(config.mode, default.mode);
+        assert_eq!(
+            config.termination.stagnation_time,
+            default.termination.stagnation_time
+        );
+        assert_eq!(
+            config.difficulty.stages.len(),
+            default.difficulty.stages.len()
+        );
+    }
+}
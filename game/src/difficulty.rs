@@ -0,0 +1,236 @@
+use crate::{enemy::EnemyType, platform::PlatformType, rng::Rng};
+
+// How the level generation evolves with height
+// Stages must be sorted by height, values are interpolated between two stages
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DifficultyProfile {
+    pub stages: Vec<DifficultyStage>,
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct DifficultyStage {
+    // Score at which this stage is fully applied
+    pub height: f64,
+    // Expected number of platforms per row (the path one + extras in the gap below it),
+    // the fractional part is the chance of an extra one
+    pub platform_density: f64,
+    // Vertical distance between two rows of platforms
+    pub min_gap: f64,
+    pub max_gap: f64,
+    pub platform_weights: PlatformWeights,
+    // Expected number of enemies per 1000 units of height
+    pub enemy_rate: f64,
+}
+
+// Relative odds of each platform type, they don't need to sum to 1
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct PlatformWeights {
+    pub green: f64,
+    pub blue: f64,
+    pub white: f64,
+    pub cracked: f64,
+    pub red: f64,
+}
+
+impl DifficultyProfile {
+    pub fn at(&self, height: f64) -> DifficultyStage {
+        let Some(first) = self.stages.first() else {
+            return DifficultyStage::default();
+        };
+
+        if height <= first.height {
+            return *first;
+        }
+
+        for window in self.stages.windows(2) {
+            let (from, to) = (window[0], window[1]);
+            if height < to.height {
+                let t = (height - from.height) / (to.height - from.height);
+                return from.lerp(&to, t);
+            }
+        }
+
+        *self.stages.last().unwrap()
+    }
+}
+
+impl Default for DifficultyProfile {
+    // Starts as an easy green staircase, then gets sparser and more hostile
+    fn default() -> Self {
+        Self {
+            stages: vec![
+                DifficultyStage {
+                    height: 0.,
+                    platform_density: 1.5,
+                    min_gap: 60.,
+                    max_gap: 140.,
+                    platform_weights: PlatformWeights {
+                        green: 1.,
+                        blue: 0.,
+                        white: 0.,
+                        cracked: 0.,
+                        red: 0.,
+                    },
+                    enemy_rate: 0.,
+                },
+                DifficultyStage {
+                    height: 5_000.,
+                    platform_density: 1.3,
+                    min_gap: 90.,
+                    max_gap: 200.,
+                    platform_weights: PlatformWeights {
+                        green: 0.7,
+                        blue: 0.15,
+                        white: 0.1,
+                        cracked: 0.05,
+                        red: 0.,
+                    },
+                    enemy_rate: 0.2,
+                },
+                DifficultyStage {
+                    height: 20_000.,
+                    platform_density: 1.1,
+                    min_gap: 130.,
+                    max_gap: 260.,
+                    platform_weights: PlatformWeights {
+                        green: 0.45,
+                        blue: 0.25,
+                        white: 0.15,
+                        cracked: 0.1,
+                        red: 0.05,
+                    },
+                    enemy_rate: 0.4,
+                },
+                DifficultyStage {
+                    height: 50_000.,
+                    platform_density: 1.,
+                    min_gap: 160.,
+                    max_gap: 300.,
+                    platform_weights: PlatformWeights {
+                        green: 0.3,
+                        blue: 0.3,
+                        white: 0.2,
+                        cracked: 0.12,
+                        red: 0.08,
+                    },
+                    enemy_rate: 0.6,
+                },
+            ],
+        }
+    }
+}
+
+impl DifficultyStage {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        let lerp = |a: f64, b: f64| a + (b - a) * t;
+
+        Self {
+            height: lerp(self.height, other.height),
+            platform_density: lerp(self.platform_density, other.platform_density),
+            min_gap: lerp(self.min_gap, other.min_gap),
+            max_gap: lerp(self.max_gap, other.max_gap),
+            platform_weights: PlatformWeights {
+                green: lerp(self.platform_weights.green, other.platform_weights.green),
+                blue: lerp(self.platform_weights.blue, other.platform_weights.blue),
+                white: lerp(self.platform_weights.white, other.platform_weights.white),
+                cracked: lerp(
+                    self.platform_weights.cracked,
+                    other.platform_weights.cracked,
+                ),
+                red: lerp(self.platform_weights.red, other.platform_weights.red),
+            },
+            enemy_rate: lerp(self.enemy_rate, other.enemy_rate),
+        }
+    }
+
+    pub fn platform_count(&self, rng: &mut Rng) -> u32 {
+        let density = self.platform_density.max(1.);
+        let extra = rng.chance(density.fract()) as u32;
+
+        density.trunc() as u32 + extra
+    }
+
+    pub fn gap(&self, rng: &mut Rng) -> f64 {
+        // A null gap would make the generator spin forever
+        rng.get_inc(self.min_gap, self.max_gap).max(1.)
+    }
+
+    pub fn platform_type(&self, rng: &mut Rng) -> PlatformType {
+        let weights = self.platform_weights;
+        let total = weights.green + weights.blue + weights.white + weights.cracked + weights.red;
+        let mut roll = rng.get_inc(0., total);
+
+        for (weight, kind) in [
+            (weights.blue, PlatformType::Blue),
+            (weights.white, PlatformType::White),
+            (weights.cracked, PlatformType::Cracked),
+            (weights.red, PlatformType::Red),
+        ] {
+            if roll < weight {
+                return kind;
+            }
+            roll -= weight;
+        }
+
+        PlatformType::Green
+    }
+
+    // Whether an enemy should be placed in a gap of the given size
+    pub fn spawn_enemy(&self, rng: &mut Rng, gap: f64) -> Option<EnemyType> {
+        rng.chance(self.enemy_rate * gap / 1000.)
+            .then(|| EnemyType::random(rng))
+    }
+}
+
+impl Default for DifficultyStage {
+    fn default() -> Self {
+        Self {
+            height: 0.,
+            platform_density: 1.,
+            min_gap: 100.,
+            max_gap: 100.,
+            platform_weights: PlatformWeights {
+                green: 1.,
+                blue: 0.,
+                white: 0.,
+                cracked: 0.,
+                red: 0.,
+            },
+            enemy_rate: 0.,
+        }
+    }
+}
+
+#[cfg(test)]
+mod profiles {
+    use super::*;
+
+    #[test]
+    fn interpolated() {
+        let stage = DifficultyProfile::default().at(2_500.);
+
+        assert_eq!(stage.min_gap, 75.);
+        assert_eq!(stage.max_gap, 170.);
+        assert_eq!(stage.platform_weights.green, 0.85);
+    }
+
+    #[test]
+    fn clamped() {
+        let profile = DifficultyProfile::default();
+
+        assert_eq!(profile.at(-100.).min_gap, 60.);
+        assert_eq!(profile.at(-100.).height, 0.);
+        assert_eq!(profile.at(1_000_000.).min_gap, 160.);
+        assert_eq!(profile.at(1_000_000.).height, 50_000.);
+        // Exactly on a stage
+        assert_eq!(profile.at(20_000.).min_gap, 130.);
+    }
+
+    #[test]
+    fn empty() {
+        let stage = DifficultyProfile { stages: Vec::new() }.at(1_000.);
+
+        assert_eq!(stage.min_gap, DifficultyStage::default().min_gap);
+        assert_eq!(stage.max_gap, DifficultyStage::default().max_gap);
+    }
+}
@@ -1 +1,171 @@
-pub struct Enemy {}
+pub const ENEMY_SIZE: f64 = 50.;
+
+const WALKER_SPEED: f64 = 80.;
+const HOVER_AMPLITUDE: f64 = 30.;
+const HOVER_FREQUENCY: f64 = 2.;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum EnemyType {
+    // Doesn't move
+    Static,
+    // Bobs up and down around its spawn point
+    Hovering,
+    // Walks from one side of the screen to the other
+    Walker,
+}
+
+impl EnemyType {
+    pub fn random(rng: &mut crate::rng::Rng) -> Self {
+        let roll = rng.next_f64();
+
+        if roll < 0.4 {
+            Self::Static
+        } else if roll < 0.7 {
+            Self::Hovering
+        } else {
+            Self::Walker
+        }
+    }
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct Enemy {
+    // Unique in its game
+    pub id: u64,
+    #[serde(with = "crate::serde_maths::rect")]
+    pub rect: maths::Rect,
+    pub kind: EnemyType,
+    #[serde(with = "crate::serde_maths::vec2")]
+    pub velocity: maths::Vec2,
+    #[serde(with = "crate::serde_maths::vec2")]
+    origin: maths::Point,
+    time: f64,
+}
+
+impl Enemy {
+    pub fn new(id: u64, center: impl Into<maths::Point>, kind: EnemyType) -> Self {
+        let center = center.into();
+
+        Self {
+            id,
+            rect: maths::Rect::new_from_center(center, (ENEMY_SIZE, ENEMY_SIZE), 0.),
+            kind,
+            velocity: match kind {
+                EnemyType::Walker => maths::Vec2::new(WALKER_SPEED, 0.),
+                _ => maths::Vec2::ZERO,
+            },
+            origin: center,
+            time: 0.,
+        }
+    }
+
+    pub fn update(&mut self, config: &crate::config::GameConfig, dt: f64) {
+        self.time += dt;
+
+        match self.kind {
+            EnemyType::Static => (),
+            EnemyType::Hovering => {
+                let y = self.origin.y + (self.time * HOVER_FREQUENCY).sin() * HOVER_AMPLITUDE;
+                self.rect
+                    .set_center(maths::Point::new(self.rect.center().x, y));
+            }
+            EnemyType::Walker => {
+                self.rect
+                    .set_center(self.rect.center() + self.velocity * dt);
+
+                if (self.rect.aa_topleft().x < 0. && self.velocity.x < 0.)
+                    || (self.rect.aa_topright().x > config.width && self.velocity.x > 0.)
+                {
+                    self.velocity.x = -self.velocity.x;
+                }
+            }
+        }
+    }
+
+    pub(crate) fn rebase(&mut self, shift: f64) {
+        crate::rebase_rect(&mut self.rect, shift);
+        self.origin.y += shift;
+    }
+
+    pub fn facing_right(&self) -> bool {
+        self.velocity.x >= 0.
+    }
+}
+
+#[cfg(test)]
+mod enemies {
+    use super::*;
+    use crate::{event::DeathCause, testing::player_at, GameConfig, GameEvent};
+
+    #[test]
+    fn walker() {
+        let config = GameConfig::default();
+        let mut enemy = Enemy::new(0, maths::Point::new(270., 100.), EnemyType::Walker);
+
+        enemy.update(&config, 0.5);
+        assert_eq!(enemy.rect.center().x, 270. + WALKER_SPEED * 0.5);
+        assert!(enemy.facing_right());
+
+        // Turns around once it reaches the side
+        let mut enemy = Enemy::new(0, maths::Point::new(510., 100.), EnemyType::Walker);
+        enemy.update(&config, 0.5);
+        assert!(!enemy.facing_right());
+    }
+
+    #[test]
+    fn hovering() {
+        let config = GameConfig::default();
+        let mut enemy = Enemy::new(0, maths::Point::new(270., 100.), EnemyType::Hovering);
+
+        enemy.update(&config, 0.25);
+        assert!(enemy.rect.center().y > 100.);
+
+        for _ in 0..100 {
+            enemy.update(&config, 0.05);
+            assert_eq!(enemy.rect.center().x, 270.);
+            assert!((enemy.rect.center().y - 100.).abs() <= HOVER_AMPLITUDE);
+        }
+    }
+
+    #[test]
+    fn static_enemy() {
+        let config = GameConfig::default();
+        let mut enemy = Enemy::new(0, maths::Point::new(270., 100.), EnemyType::Static);
+
+        enemy.update(&config, 1.);
+        assert_eq!(enemy.rect.center().x, 270.);
+        assert_eq!(enemy.rect.center().y, 100.);
+    }
+
+    #[test]
+    fn stomp() {
+        let config = GameConfig::default();
+        let enemies = [Enemy::new(
+            0,
+            maths::Point::new(270., 530.),
+            EnemyType::Static,
+        )];
+        let mut player = player_at(270., 480., (0., 400.));
+
+        player.update(&config, &[], &enemies, &[], 0.05);
+        assert!(!player.level.is_alive(&enemies[0]));
+        assert!(player.death.is_none());
+        assert!(player.velocity.y < 0.);
+        assert_eq!(player.events(), [GameEvent::EnemyKilled, GameEvent::Jumped]);
+    }
+
+    #[test]
+    fn side_contact() {
+        let config = GameConfig::default();
+        let enemies = [Enemy::new(
+            0,
+            maths::Point::new(290., 480.),
+            EnemyType::Static,
+        )];
+        let mut player = player_at(270., 480., (0., 0.));
+
+        player.update(&config, &[], &enemies, &[], 0.05);
+        assert!(player.level.is_alive(&enemies[0]));
+        assert_eq!(player.death, Some(DeathCause::Enemy));
+    }
+}
@@ -0,0 +1,183 @@
+// Agent-facing wrapper around a game: pick an action, step, read what happened
+// The observation is built by the agent's own function, so any input layout can plug in
+// The agent plays the first player of the game
+
+pub struct Step<O> {
+    pub observation: O,
+    // Score gained during the step
+    pub reward: f32,
+    // The game is over, call Env::reset to play again
+    pub done: bool,
+    pub info: Info,
+    pub events: Vec<crate::GameEvent>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Info {
+    pub score: f32,
+    pub ticks: u64,
+    pub seed: u64,
+    pub terminal: Option<crate::TerminalReason>,
+}
+
+// The observation function can capture anything, a SensorConfig picked at runtime for example
+type Observe<O> = Box<dyn Fn(&crate::Game) -> O + Send + Sync>;
+
+pub struct Env<O> {
+    game: crate::Game,
+    observe: Observe<O>,
+    replay: Option<crate::replay::Replay>,
+}
+
+impl<O> Env<O> {
+    pub fn new(
+        game: crate::Game,
+        observe: impl Fn(&crate::Game) -> O + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            game,
+            observe: Box::new(observe),
+            replay: None,
+        }
+    }
+
+    // Records every step from now on, restarted at each reset
+    pub fn recording(mut self) -> Self {
+        self.replay = Some(crate::replay::Replay::new(&self.game));
+        self
+    }
+
+    // Starts a new game with the same config and player count, None -> random level
+    pub fn reset(&mut self, seed: Option<u64>) -> O {
+        let config = self.game.config.clone();
+
+        let seed = seed.unwrap_or_else(|| crate::rng::Rng::from_time().seed());
+
+        self.game = crate::Game::with_players(config, seed, self.game.players.len());
+
+        if let Some(replay) = self.replay.as_mut() {
+            *replay = crate::replay::Replay::new(&self.game);
+        }
+
+        self.observe()
+    }
+
+    // Plays the action for exactly one tick
+    pub fn step(&mut self, action: crate::Action) -> Step<O> {
+        let score = self.game.players[0].exact_score();
+
+        self.game.set_action(0, action);
+        match self.replay.as_mut() {
+            Some(replay) => replay.record(&mut self.game),
+            None => self.game.tick(),
+        }
+
+        Step {
+            observation: self.observe(),
+            reward: (self.game.players[0].exact_score() - score) as f32,
+            done: self.game.is_over(),
+            info: self.info(),
+            events: self.game.players[0].events().to_vec(),
+        }
+    }
+
+    // Same as Game::accumulate, for front-ends running in real time
+    pub fn accumulate(&mut self, dt: f64) -> u32 {
+        self.game.accumulate(dt)
+    }
+
+    pub fn observe(&self) -> O {
+        (self.observe)(&self.game)
+    }
+
+    pub fn info(&self) -> Info {
+        Info {
+            score: self.game.players[0].score(),
+            ticks: self.game.ticks,
+            seed: self.game.seed(),
+            terminal: self.game.players[0].terminal,
+        }
+    }
+
+    pub fn game(&self) -> &crate::Game {
+        &self.game
+    }
+
+    pub fn replay(&self) -> Option<&crate::replay::Replay> {
+        self.replay.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod envs {
+    use super::*;
+    use crate::{Action, Game, GameConfig, TerminalReason};
+
+    fn env(max_ticks: Option<u64>) -> Env<u64> {
+        let mut config = GameConfig::default();
+        config.termination.max_ticks = max_ticks;
+
+        Env::new(Game::with_seed(config, 1), |game| game.ticks).recording()
+    }
+
+    #[test]
+    fn rewards_add_up_to_the_score() {
+        let mut env = env(None);
+        let start = env.game().players[0].exact_score();
+        let mut total = 0.;
+
+        for tick in 0..200 {
+            let step = env.step(Action::ALL[tick / 6 % Action::COUNT]);
+            total += step.reward as f64;
+            if step.done {
+                break;
+            }
+        }
+
+        assert!((start + total - env.game().players[0].exact_score()).abs() < 1e-2);
+    }
+
+    #[test]
+    fn done() {
+        let mut env = env(Some(10));
+
+        for tick in 1..=10 {
+            let step = env.step(Action::Right);
+
+            assert_eq!(step.observation, tick);
+            assert_eq!(step.info.ticks, tick);
+            assert_eq!(step.done, tick == 10);
+        }
+        assert_eq!(env.info().terminal, Some(TerminalReason::MaxTicks));
+
+        let replay = env.replay().unwrap();
+        assert_eq!(replay.actions.len(), 10);
+        assert_eq!(replay.verify().unwrap().ticks, 10);
+    }
+
+    #[test]
+    fn capturing_observation() {
+        let sensors = crate::sensor::SensorConfig {
+            angles: vec![0., 180.],
+            max_distance: 500.,
+        };
+        let mut env = Env::new(Game::with_seed(GameConfig::default(), 1), move |game| {
+            game.sensors(0, &sensors).len()
+        });
+
+        assert_eq!(env.observe(), 2);
+        assert_eq!(env.step(Action::None).observation, 2);
+    }
+
+    #[test]
+    fn reset() {
+        let mut env = env(Some(10));
+        env.step(Action::Left);
+
+        assert_eq!(env.reset(Some(5)), 0);
+        assert_eq!(env.info().seed, 5);
+        assert_eq!(env.info().ticks, 0);
+        assert!(env.replay().unwrap().actions.is_empty());
+        assert_eq!(env.replay().unwrap().seed, 5);
+    }
+}
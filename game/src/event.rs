@@ -0,0 +1,26 @@
+// Things that happened during a tick, see Player::events and the return value of Game::update
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum DeathCause {
+    // Went below the screen
+    Fell,
+    // Touched an enemy without landing on it
+    Enemy,
+    // Touched a red platform
+    Hazard,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum GameEvent {
+    // Bounced on anything, platform, power-up or enemy
+    Jumped,
+    // Landed on top of the platform with this id
+    LandedOn(u64),
+    // Went through a side of the screen
+    Wrapped,
+    // The platform with this id was destroyed by the player
+    PlatformBroken(u64),
+    // By a stomp or a projectile
+    EnemyKilled,
+    Died(DeathCause),
+}
@@ -0,0 +1,344 @@
+// Physics-aware checks used by the level generation, so every level has a reachable path
+use crate::{
+    config::GameConfig,
+    platform::{Platform, PlatformType},
+    player::MovementModel,
+};
+
+// The simulation runs in discrete ticks, so don't rely on the exact limits
+const SAFETY_MARGIN: f64 = 0.85;
+
+// Closest two rows can be, the player must fit between them
+pub fn min_vertical_spacing(config: &GameConfig) -> f64 {
+    config.platform_height + config.player_size
+}
+
+// Height of a jump from a regular platform
+pub fn max_jump_height(config: &GameConfig) -> f64 {
+    config.jump_height * config.jump_height / (2. * config.gravity)
+}
+
+// Furthest two rows can be while staying reachable
+pub fn max_vertical_spacing(config: &GameConfig) -> f64 {
+    max_jump_height(config) * SAFETY_MARGIN
+}
+
+// Horizontal distance, taking the screen wrap into account if the mode has one
+pub fn wrapped_distance(config: &GameConfig, a: f64, b: f64) -> f64 {
+    if !config.mode.wraps() {
+        return (a - b).abs();
+    }
+
+    let d = (a - b).abs() % config.width;
+    d.min(config.width - d)
+}
+
+// Time spent in the air between bouncing and landing `height` higher
+// None if the jump can't get that high
+pub fn air_time(config: &GameConfig, height: f64) -> Option<f64> {
+    let discriminant = config.jump_height * config.jump_height - 2. * config.gravity * height;
+    if discriminant < 0. {
+        return None;
+    }
+    Some((config.jump_height + discriminant.sqrt()) / config.gravity)
+}
+
+// Horizontal distance covered by holding a direction for that long, from a standstill
+pub fn run_distance(config: &GameConfig, time: f64) -> f64 {
+    if let MovementModel::Digital = config.movement {
+        return config.speed * time;
+    }
+
+    // Tick by tick, the same way the player moves
+    let mut velocity = 0.;
+    let mut distance = 0.;
+    for _ in 0..(time / crate::TICK_DELTA_TIME) as u64 {
+        distance += velocity * crate::TICK_DELTA_TIME;
+        velocity =
+            config
+                .movement
+                .next_velocity(velocity, 1., config.speed, crate::TICK_DELTA_TIME);
+    }
+
+    distance
+}
+
+// Furthest a platform `height` higher can be horizontally while staying reachable
+pub fn max_horizontal_reach(config: &GameConfig, height: f64) -> f64 {
+    let Some(air_time) = air_time(config, height / SAFETY_MARGIN) else {
+        return 0.;
+    };
+    // Landing on the edge of the platform is enough
+    run_distance(config, air_time) * SAFETY_MARGIN
+        + (config.platform_width + config.player_size) / 2.
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Jump {
+    // Positive when going up
+    pub height: f64,
+    pub distance: f64,
+    pub air_time: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JumpError {
+    TooHigh { height: f64, max: f64 },
+    TooFar { distance: f64, max: f64 },
+}
+
+impl std::fmt::Display for JumpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooHigh { height, max } => write!(f, "too high ({height:.0} > {max:.0})"),
+            Self::TooFar { distance, max } => write!(f, "too far ({distance:.0} > {max:.0})"),
+        }
+    }
+}
+
+impl std::error::Error for JumpError {}
+
+// Whether the player can bounce from a platform at `from` and land on one at `to`
+pub fn jump(config: &GameConfig, from: maths::Point, to: maths::Point) -> Result<Jump, JumpError> {
+    let height = from.y - to.y;
+    let distance = wrapped_distance(config, from.x, to.x);
+
+    if height > max_vertical_spacing(config) {
+        return Err(JumpError::TooHigh {
+            height,
+            max: max_vertical_spacing(config),
+        });
+    }
+
+    let max_reach = max_horizontal_reach(config, height);
+    if distance > max_reach {
+        return Err(JumpError::TooFar {
+            distance,
+            max: max_reach,
+        });
+    }
+
+    Ok(Jump {
+        height,
+        distance,
+        air_time: air_time(config, height).unwrap_or_default(),
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Step {
+    // Indices in the checked slice
+    pub from: usize,
+    pub to: usize,
+    pub jump: Jump,
+}
+
+// Proof that a layout can be climbed: every jump from the lowest to the highest platform
+#[derive(Debug, Clone)]
+pub struct LayoutReport {
+    pub path: Vec<Step>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LayoutError {
+    NoPlatform,
+    // Nothing reachable above that height
+    Unreachable { stuck_at: f64, target: f64 },
+}
+
+impl std::fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoPlatform => write!(f, "No platform to stand on"),
+            Self::Unreachable { stuck_at, target } => write!(
+                f,
+                "Can't go higher than {:.0}, the highest platform is at {:.0}",
+                -stuck_at, -target
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+// Moving, breaking and deadly platforms can't be relied on
+pub fn is_dependable(platform: &Platform) -> bool {
+    matches!(platform.kind, PlatformType::Green | PlatformType::White)
+}
+
+// Checks that the highest dependable platform can be reached from the lowest one
+pub fn validate(config: &GameConfig, platforms: &[Platform]) -> Result<LayoutReport, LayoutError> {
+    // Lowest first
+    let mut order = platforms
+        .iter()
+        .enumerate()
+        .filter(|(_, platform)| is_dependable(platform))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    order.sort_by(|a, b| {
+        platforms[*b]
+            .rect
+            .center()
+            .y
+            .total_cmp(&platforms[*a].rect.center().y)
+    });
+
+    let (Some(&start), Some(&target)) = (order.first(), order.last()) else {
+        return Err(LayoutError::NoPlatform);
+    };
+
+    // Best jump that led to each platform
+    let mut reached_by: Vec<Option<Step>> = vec![None; platforms.len()];
+    let mut reached = vec![false; platforms.len()];
+    reached[start] = true;
+    let mut highest = start;
+
+    for (n, &from) in order.iter().enumerate() {
+        if !reached[from] {
+            continue;
+        }
+        if platforms[from].rect.center().y < platforms[highest].rect.center().y {
+            highest = from;
+        }
+
+        for &to in order.iter().skip(n + 1) {
+            if reached[to] {
+                continue;
+            }
+            if let Ok(jump) = jump(
+                config,
+                platforms[from].rect.center(),
+                platforms[to].rect.center(),
+            ) {
+                reached[to] = true;
+                reached_by[to] = Some(Step { from, to, jump });
+            }
+        }
+    }
+
+    if !reached[target] {
+        return Err(LayoutError::Unreachable {
+            stuck_at: platforms[highest].rect.center().y,
+            target: platforms[target].rect.center().y,
+        });
+    }
+
+    let mut path = Vec::new();
+    let mut current = target;
+    while let Some(step) = reached_by[current] {
+        path.push(step);
+        current = step.from;
+    }
+    path.reverse();
+
+    Ok(LayoutReport { path })
+}
+
+#[cfg(test)]
+mod layouts {
+    use super::*;
+    use crate::{difficulty::DifficultyProfile, Game};
+
+    #[test]
+    fn reachable() {
+        let config = GameConfig::default();
+
+        let jump = jump(
+            &config,
+            maths::Point::new(270., 500.),
+            maths::Point::new(300., 400.),
+        )
+        .unwrap();
+        assert_eq!(jump.height, 100.);
+        assert_eq!(jump.distance, 30.);
+        assert!(jump.air_time > 0.);
+    }
+
+    #[test]
+    fn too_high() {
+        let config = GameConfig::default();
+
+        assert!(matches!(
+            jump(
+                &config,
+                maths::Point::new(270., 500.),
+                maths::Point::new(270., 0.)
+            ),
+            Err(JumpError::TooHigh { height, .. }) if height == 500.
+        ));
+    }
+
+    #[test]
+    fn too_far() {
+        let config = GameConfig {
+            speed: 50.,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            jump(
+                &config,
+                maths::Point::new(10., 500.),
+                maths::Point::new(260., 400.)
+            ),
+            Err(JumpError::TooFar { distance, .. }) if distance == 250.
+        ));
+    }
+
+    #[test]
+    fn inertial_reach() {
+        let digital = GameConfig::default();
+        let slow = GameConfig {
+            movement: MovementModel::Inertial {
+                acceleration: 20.,
+                friction: 0.,
+            },
+            ..Default::default()
+        };
+        let fast = GameConfig {
+            movement: MovementModel::Inertial {
+                acceleration: 1e9,
+                friction: 0.,
+            },
+            ..Default::default()
+        };
+
+        let reach = max_horizontal_reach(&digital, 100.);
+        assert!(max_horizontal_reach(&slow, 100.) < reach);
+        // Only the first tick is lost
+        let lost = digital.speed * crate::TICK_DELTA_TIME * SAFETY_MARGIN;
+        assert!(max_horizontal_reach(&fast, 100.) >= reach - 2. * lost);
+
+        // Close enough at full speed, too far when starting from a standstill
+        let (from, to) = (maths::Point::new(10., 500.), maths::Point::new(260., 400.));
+        assert!(jump(&digital, from, to).is_ok());
+        assert!(matches!(
+            jump(&slow, from, to),
+            Err(JumpError::TooFar { .. })
+        ));
+    }
+
+    // Every difficulty, held for the whole level, must give a level that can be climbed
+    #[test]
+    fn many_seeds() {
+        for height in [0., 5_000., 20_000., 50_000., 100_000.] {
+            let config = GameConfig {
+                difficulty: DifficultyProfile {
+                    stages: vec![DifficultyProfile::default().at(height)],
+                },
+                ..Default::default()
+            };
+
+            for seed in 0..10 {
+                let mut game = Game::with_seed(config.clone(), seed);
+                for _ in 0..100 {
+                    game.generate_row();
+                }
+
+                if let Err(e) = game.layout_report() {
+                    panic!("Seed {seed} at height {height}: {e}");
+                }
+            }
+        }
+    }
+}
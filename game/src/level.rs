@@ -0,0 +1,165 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{enemy::Enemy, platform::Platform, power_up::PowerUp};
+
+const BROKEN_PLATFORM_FALL_SPEED: f64 = 600.;
+// Time spent on each frame of the cracking animation (CrackedPlatform0..3 textures)
+const CRACK_STAGE_TIME: f64 = 0.1;
+const CRACK_STAGES: u8 = 4;
+
+// What a player changed in the level, by id
+// The level is shared, but a platform broken by one player is still there for the others
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct LevelState {
+    // White platforms the player bounced on
+    removed: BTreeSet<u64>,
+    // Cracked platforms that broke, with the time since
+    broken: BTreeMap<u64, f64>,
+    killed: BTreeSet<u64>,
+    // Springs and trampolines still work after that
+    used: BTreeSet<u64>,
+}
+
+impl LevelState {
+    // Whether the player can interact with it
+    pub fn is_solid(&self, platform: &Platform) -> bool {
+        !self.removed.contains(&platform.id) && !self.broken.contains_key(&platform.id)
+    }
+
+    // 0 while intact, then goes through each stage of the animation
+    pub fn crack_stage(&self, platform: &Platform) -> u8 {
+        match self.broken.get(&platform.id) {
+            None => 0,
+            Some(t) => ((t / CRACK_STAGE_TIME) as u8 + 1).min(CRACK_STAGES - 1),
+        }
+    }
+
+    // How far a broken platform fell, to draw it
+    pub fn fall(&self, platform: &Platform) -> f64 {
+        self.broken
+            .get(&platform.id)
+            .map_or(0., |t| t * BROKEN_PLATFORM_FALL_SPEED)
+    }
+
+    pub fn is_removed(&self, platform: &Platform) -> bool {
+        self.removed.contains(&platform.id)
+    }
+
+    pub fn is_alive(&self, enemy: &Enemy) -> bool {
+        !self.killed.contains(&enemy.id)
+    }
+
+    pub fn is_used(&self, power_up: &PowerUp) -> bool {
+        self.used.contains(&power_up.id)
+    }
+
+    // Whether the player can still touch it
+    pub fn is_available(&self, power_up: &PowerUp) -> bool {
+        power_up.kind.is_attached() || !self.is_used(power_up)
+    }
+
+    pub(crate) fn remove(&mut self, platform: &Platform) {
+        self.removed.insert(platform.id);
+    }
+
+    // Cracking it again doesn't restart the animation
+    pub(crate) fn crack(&mut self, platform: &Platform) {
+        self.broken.entry(platform.id).or_insert(0.);
+    }
+
+    pub(crate) fn kill(&mut self, enemy: &Enemy) {
+        self.killed.insert(enemy.id);
+    }
+
+    pub(crate) fn use_power_up(&mut self, power_up: &PowerUp) {
+        self.used.insert(power_up.id);
+    }
+
+    pub(crate) fn update(&mut self, dt: f64) {
+        for broken_since in self.broken.values_mut() {
+            *broken_since += dt;
+        }
+    }
+
+    // Forgets what isn't in the level anymore
+    pub(crate) fn retain(
+        &mut self,
+        platforms: &[Platform],
+        enemies: &[Enemy],
+        power_ups: &[PowerUp],
+    ) {
+        let platform_ids = platforms
+            .iter()
+            .map(|platform| platform.id)
+            .collect::<BTreeSet<_>>();
+        self.removed.retain(|id| platform_ids.contains(id));
+        self.broken.retain(|id, _| platform_ids.contains(id));
+
+        let enemy_ids = enemies
+            .iter()
+            .map(|enemy| enemy.id)
+            .collect::<BTreeSet<_>>();
+        self.killed.retain(|id| enemy_ids.contains(id));
+
+        let power_up_ids = power_ups
+            .iter()
+            .map(|power_up| power_up.id)
+            .collect::<BTreeSet<_>>();
+        self.used.retain(|id| power_up_ids.contains(id));
+    }
+}
+
+#[cfg(test)]
+mod level_state {
+    use super::*;
+    use crate::{platform::PlatformType, testing::platform};
+
+    #[test]
+    fn crack_progression() {
+        let platform = platform(0, 270., 500., PlatformType::Cracked);
+        let mut level = LevelState::default();
+        assert!(level.is_solid(&platform));
+        assert_eq!(level.crack_stage(&platform), 0);
+
+        level.crack(&platform);
+        assert!(!level.is_solid(&platform));
+        assert_eq!(level.crack_stage(&platform), 1);
+
+        level.update(0.1);
+        assert_eq!(level.crack_stage(&platform), 2);
+        assert!(level.fall(&platform) > 0.);
+
+        // Cracking it again doesn't restart the animation
+        level.crack(&platform);
+        assert_eq!(level.crack_stage(&platform), 2);
+
+        level.update(1.);
+        assert_eq!(level.crack_stage(&platform), CRACK_STAGES - 1);
+    }
+
+    #[test]
+    fn removed() {
+        let platform = platform(0, 270., 500., PlatformType::White);
+        let mut level = LevelState::default();
+        assert!(level.is_solid(&platform));
+
+        level.remove(&platform);
+        assert!(!level.is_solid(&platform));
+        assert!(level.is_removed(&platform));
+    }
+
+    #[test]
+    fn forgets_what_left_the_level() {
+        let platforms = [
+            platform(0, 270., 500., PlatformType::White),
+            platform(1, 270., 300., PlatformType::Cracked),
+        ];
+        let mut level = LevelState::default();
+        level.remove(&platforms[0]);
+        level.crack(&platforms[1]);
+
+        level.retain(&platforms[1..], &[], &[]);
+        assert!(level.is_solid(&platforms[0]));
+        assert!(!level.is_solid(&platforms[1]));
+    }
+}
@@ -1,118 +1,758 @@
+pub use action::Action;
+pub use config::GameConfig;
 use enemy::Enemy;
+pub use env::Env;
+pub use event::GameEvent;
 use platform::Platform;
 use player::Player;
+use power_up::PowerUp;
+pub use termination::TerminalReason;
 
+pub mod action;
+pub mod camera;
+pub mod config;
+pub mod difficulty;
 pub mod enemy;
+pub mod env;
+pub mod event;
+pub mod generator;
+pub mod level;
+pub mod mode;
 pub mod platform;
 pub mod player;
+pub mod power_up;
+pub mod projectile;
+pub mod query;
+pub mod replay;
+pub mod rng;
+pub mod sensor;
+mod serde_maths;
+pub mod stats;
+pub mod termination;
+#[cfg(test)]
+mod testing;
+pub mod track;
 
-const PLATFORM_LIMIT: u32 = 5;
+// Chance for a new green platform to hold a power-up
+const POWER_UP_CHANCE: f64 = 0.08;
 
-pub const GAME_WIDTH: f64 = 540.;
-pub const GAME_HEIGHT: f64 = 960.;
+// The simulation always advances by this step, whatever the caller's frame rate is
+pub const TICK_RATE: u32 = 20;
+pub const TICK_DELTA_TIME: f64 = 1. / TICK_RATE as f64;
+// If the caller lags behind more than that, the remaining time is dropped instead of catching up
+const MAX_TICKS_PER_UPDATE: u32 = 8;
 
+// Once the highest camera is that far above y = 0, everything is moved back down by a multiple of it
+// It's a power of two so moving a position back near 0 doesn't round it
+const REBASE_STEP: f64 = 8192.;
+
+// Clone it to branch a simulation, serialize it to save the exact state of a run
+// Every player climbs the same level, but only sees what it broke and killed itself, see Player::level
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct Game {
     // fk getters and setters
     pub enemies: Vec<Enemy>,
     pub platforms: Vec<Platform>,
-    pub player: Player,
-    pub scroll: i32,
-    pub lost: bool,
+    pub power_ups: Vec<PowerUp>,
+    pub players: Vec<Player>,
+    pub config: GameConfig,
+    pub ticks: u64,
+    // Last platform of the guaranteed path, the next one is generated from it
+    #[serde(with = "crate::serde_maths::vec2")]
+    last_path: maths::Point,
+    accumulator: f64,
+    rng: rng::Rng,
+    next_platform_id: u64,
+    next_enemy_id: u64,
+    next_power_up_id: u64,
+    // World height of y = 0, positions are kept near 0 so long runs don't lose precision
+    origin: f64,
+    // What's left to place of GameConfig::track, None once the generator took over
+    track: Option<Vec<track::TrackItem>>,
 }
 
 impl Game {
-    pub fn new() -> Self {
+    pub fn new(config: GameConfig) -> Self {
+        Self::with_rng(config, rng::Rng::from_time(), 1)
+    }
+
+    pub fn with_seed(config: GameConfig, seed: u64) -> Self {
+        Self::with_rng(config, rng::Rng::new(seed), 1)
+    }
+
+    // Several players on the same level, they all start at the same place
+    pub fn with_players(config: GameConfig, seed: u64, count: usize) -> Self {
+        Self::with_rng(config, rng::Rng::new(seed), count.max(1))
+    }
+
+    fn with_rng(config: GameConfig, mut rng: rng::Rng, player_count: usize) -> Self {
         let mut platforms = Vec::new();
+        let player = Player::new(&config);
+        // The generation starts from the highest one, so there must be at least one
+        // A track has its own start
+        let platform_limit = if config.track.is_some() {
+            0
+        } else {
+            config.platform_limit.max(1)
+        };
 
-        for i in 1..=PLATFORM_LIMIT {
-            let size = maths::Vec2::new(
-                platform::PLATFORM_BASE_WIDTH,
-                platform::PLATFORM_BASE_HEIGHT,
-            );
-            let pos = maths::Point::new(
-                random::get_inc(
-                    platform::PLATFORM_BASE_WIDTH,
-                    GAME_WIDTH - platform::PLATFORM_BASE_WIDTH,
-                ),
-                ((GAME_HEIGHT as u32 / PLATFORM_LIMIT) * i) as f64,
-            );
+        for i in 1..=platform_limit {
+            let size = maths::Vec2::new(config.platform_width, config.platform_height);
+            let y = ((config.height as u32 / platform_limit) * i) as f64;
+            // The player falls straight on the first platform below it
+            let x = if y > player.rect.center().y
+                && platforms
+                    .iter()
+                    .all(|p: &Platform| p.rect.center().y <= player.rect.center().y)
+            {
+                player.rect.center().x
+            } else {
+                rng.get_inc(config.platform_width, config.width - config.platform_width)
+            };
+            let pos = maths::Point::new(x, y);
 
-            platforms.push(Platform::new(maths::Rect::new_from_center(pos, size, 0.)));
+            // Keep the start safe
+            platforms.push(Platform::new(
+                i as u64 - 1,
+                maths::Rect::new_from_center(pos, size, 0.),
+                platform::PlatformType::Green,
+            ));
         }
 
-        Self {
+        // Highest one
+        let last_path = platforms
+            .first()
+            .map_or(player.rect.center(), |platform| platform.rect.center());
+        let track = config.track.as_ref().map(track::Track::items);
+
+        let mut game = Self {
             enemies: Vec::new(),
             platforms,
-            player: Player::new(),
-            scroll: 0,
-            lost: false,
+            power_ups: Vec::new(),
+            players: vec![player; player_count],
+            config,
+            ticks: 0,
+            last_path,
+            accumulator: 0.,
+            rng,
+            next_platform_id: platform_limit as u64,
+            next_enemy_id: 0,
+            next_power_up_id: 0,
+            origin: 0.,
+            track,
+        };
+        game.place_track(0.);
+
+        game
+    }
+
+    // Adds real time to the game and returns how many fixed ticks are due
+    // Use it with Game::tick to pick an input before each tick, like the training does
+    pub fn accumulate(&mut self, dt: f64) -> u32 {
+        self.accumulator += dt;
+
+        let mut ticks = 0;
+        while self.accumulator >= TICK_DELTA_TIME {
+            if ticks == MAX_TICKS_PER_UPDATE {
+                self.accumulator = 0.;
+                break;
+            }
+            self.accumulator -= TICK_DELTA_TIME;
+            ticks += 1;
         }
+
+        ticks
     }
 
-    pub fn update(&mut self, dt: f64) {
-        if self.lost {
+    // Runs every tick due for the given real time, the current inputs are held during all of them
+    // Returns the events of all those ticks, with the index of the player they happened to
+    pub fn update(&mut self, dt: f64) -> Vec<(usize, GameEvent)> {
+        let actions = self
+            .players
+            .iter()
+            .map(|player| player.action)
+            .collect::<Vec<_>>();
+        let mut events = Vec::new();
+
+        for _ in 0..self.accumulate(dt) {
+            for (player, action) in self.players.iter_mut().zip(actions.iter()) {
+                player.action = *action;
+            }
+            self.tick();
+
+            for (i, player) in self.players.iter().enumerate() {
+                events.extend(player.events().iter().map(|event| (i, *event)));
+            }
+        }
+
+        events
+    }
+
+    // Advances the simulation by exactly one TICK_DELTA_TIME
+    pub fn tick(&mut self) {
+        for player in self.players.iter_mut() {
+            player.clear_events();
+        }
+
+        if self.is_over() {
             // println!("{}", self.score());
             return;
         }
-        // assert_eq!(self.platforms.len(), PLATFORM_LIMIT as usize);
+        // assert_eq!(self.platforms.len(), self.config.platform_limit as usize);
 
-        // remove platforms
-        if self.player.rect.center().y - self.scroll as f64 - GAME_HEIGHT > 0. {
-            // println!("Failled");
-            self.lost = true;
+        self.rebase_if_needed();
+
+        // The level is kept from the lowest camera to the highest one
+        let (top, bottom) = self.scroll_range();
+
+        for player in self.players.iter_mut().filter(|player| !player.is_over()) {
+            if player.is_below_screen(&self.config) {
+                // println!("Failled");
+                player.die(event::DeathCause::Fell);
+            }
         }
 
+        // remove platforms
         self.platforms.retain(|platform| {
             // maths::get_distance(platform.rect.center(), self.player.rect.center()) < 1000.
-            platform.rect.center().y - (self.scroll as f64) < GAME_HEIGHT
+            platform.rect.center().y - bottom < self.config.height
         });
 
         // create platforms (remove platfoms first to not iter over newly created platforms)
+        self.place_track(top);
+        // Rows are generated until one is above the screen
+        while self.track.is_none() && self.last_path.y > top {
+            self.generate_row();
+        }
 
-        while (self.platforms.len() as u32) < PLATFORM_LIMIT {
-            let pos = maths::Point::new(
-                random::get_inc(
-                    platform::PLATFORM_BASE_WIDTH / 2.,
-                    GAME_WIDTH - platform::PLATFORM_BASE_WIDTH / 2.,
-                ),
-                self.scroll as f64,
-            );
-            let size = maths::Vec2::new(
-                platform::PLATFORM_BASE_WIDTH,
-                platform::PLATFORM_BASE_HEIGHT,
-            );
+        self.power_ups
+            .retain(|power_up| power_up.rect.center().y - bottom < self.config.height);
 
-            let rect = maths::Rect::new_from_center(pos, size, 0.);
+        self.enemies
+            .retain(|enemy| enemy.rect.center().y - bottom < self.config.height);
+
+        for player in self.players.iter_mut() {
+            player
+                .level
+                .retain(&self.platforms, &self.enemies, &self.power_ups);
+            player.level.update(TICK_DELTA_TIME);
+        }
+
+        for platform in self.platforms.iter_mut() {
+            platform.update(&self.config, TICK_DELTA_TIME);
+        }
+
+        for enemy in self.enemies.iter_mut() {
+            enemy.update(&self.config, TICK_DELTA_TIME);
+        }
+
+        // Players that fell during this tick aren't updated
+        for player in self.players.iter_mut().filter(|player| !player.is_over()) {
+            player.update_projectiles(&self.config, &self.enemies, TICK_DELTA_TIME);
+
+            player.update(
+                &self.config,
+                &self.platforms,
+                &self.enemies,
+                &self.power_ups,
+                TICK_DELTA_TIME,
+            );
 
-            self.platforms.push(Platform::new(rect));
+            player.update_camera(&self.config, TICK_DELTA_TIME);
         }
 
-        // update player
+        self.ticks += 1;
 
-        self.player.update(&self.platforms, dt);
+        for player in self.players.iter_mut() {
+            player.check_mode(&self.config.mode, self.ticks);
+            player.check_termination(&self.config.termination, self.ticks, TICK_DELTA_TIME);
+        }
 
-        // update 'camera'
-        // let t = 20.0 * dt;
-        // self.scroll = (self.scroll as f64 * (1. - t) + self.player.rect.center().y - (GAME_HEIGHT / 2.) * t) as u32;
-        let new_scroll = (self.player.rect.center().y - (GAME_HEIGHT / 2.)) as i32;
-        if new_scroll < self.scroll {
-            self.scroll = new_scroll;
+        if let Some(finish) = self.finish_line() {
+            for player in self.players.iter_mut() {
+                if !player.is_over() && player.rect.center().y < finish {
+                    player.terminal = Some(TerminalReason::TrackCompleted);
+                }
+            }
         }
 
         // println!("{}", self.score());
     }
 
-    pub fn player_move_left(&mut self) {
-        self.player.current_direction = Some(false)
+    fn rebase_if_needed(&mut self) {
+        let (top, _) = self.scroll_range();
+
+        if top < -REBASE_STEP {
+            self.rebase((-top / REBASE_STEP).floor() * REBASE_STEP);
+        }
+    }
+
+    // Moves the whole level down by that distance, nothing changes for the players
+    pub(crate) fn rebase(&mut self, shift: f64) {
+        for platform in self.platforms.iter_mut() {
+            rebase_rect(&mut platform.rect, shift);
+        }
+        for power_up in self.power_ups.iter_mut() {
+            rebase_rect(&mut power_up.rect, shift);
+        }
+        for enemy in self.enemies.iter_mut() {
+            enemy.rebase(shift);
+        }
+        for player in self.players.iter_mut() {
+            player.rebase(shift);
+        }
+
+        self.last_path.y += shift;
+        self.origin += shift;
+    }
+
+    // Highest and lowest scroll of the players still playing
+    fn scroll_range(&self) -> (f64, f64) {
+        let scrolls = self
+            .players
+            .iter()
+            .filter(|player| !player.is_over())
+            .map(|player| player.camera.top());
+
+        scrolls.fold(
+            (f64::INFINITY, f64::NEG_INFINITY),
+            |(top, bottom), scroll| (top.min(scroll), bottom.max(scroll)),
+        )
     }
 
-    pub fn player_move_right(&mut self) {
-        self.player.current_direction = Some(true)
+    // Local y of something at that height on the track
+    fn track_y(&self, height: f64) -> f64 {
+        self.config.height - height + self.origin
     }
 
-    pub fn player_shoot(&mut self) {}
+    // Places everything on the track up to a screen above the given y
+    fn place_track(&mut self, top: f64) {
+        let Some(mut items) = self.track.take() else {
+            return;
+        };
+
+        while let Some(item) = items.pop() {
+            let y = self.track_y(item.height());
+            if y <= top - self.config.height {
+                items.push(item);
+                self.track = Some(items);
+                return;
+            }
+
+            match item {
+                track::TrackItem::Platform(platform) => {
+                    let size = platform.size.map_or(
+                        maths::Vec2::new(self.config.platform_width, self.config.platform_height),
+                        |[width, height]| maths::Vec2::new(width, height),
+                    );
+                    self.platforms.push(Platform::new(
+                        self.next_platform_id,
+                        maths::Rect::new_from_center(maths::Point::new(platform.x, y), size, 0.),
+                        platform.kind,
+                    ));
+                    self.next_platform_id += 1;
+                }
+                track::TrackItem::Enemy(enemy) => {
+                    self.enemies.push(Enemy::new(
+                        self.next_enemy_id,
+                        maths::Point::new(enemy.x, y),
+                        enemy.kind,
+                    ));
+                    self.next_enemy_id += 1;
+                }
+                track::TrackItem::PowerUp(power_up) => {
+                    self.power_ups.push(PowerUp::new(
+                        self.next_power_up_id,
+                        maths::Point::new(power_up.x, y),
+                        power_up.kind,
+                    ));
+                    self.next_power_up_id += 1;
+                }
+            }
+        }
+
+        // Everything is placed
+        self.track = Some(items);
+        let Some(config) = self.config.track.as_ref() else {
+            return;
+        };
+        if config.end == track::TrackEnd::Procedural {
+            if let Some(top) = config.top() {
+                self.last_path = maths::Point::new(top.x, self.track_y(top.height));
+            }
+            self.track = None;
+        }
+    }
+
+    // Local y players must get above to complete the track, if it has an end
+    fn finish_line(&self) -> Option<f64> {
+        let track = self.config.track.as_ref()?;
+        if track.end != track::TrackEnd::Finish {
+            return None;
+        }
+
+        track.top().map(|top| self.track_y(top.height))
+    }
+
+    // Places a platform the player can always reach from the previous one,
+    // then fills the gap between them with extra platforms and enemies
+    fn generate_row(&mut self) {
+        use platform::PlatformType;
+
+        let width = self.config.width;
+        let platform_width = self.config.platform_width;
+        let min_spacing = generator::min_vertical_spacing(&self.config);
+
+        let stage = self.config.difficulty.at(self.origin - self.last_path.y);
+        let gap = stage
+            .gap(&mut self.rng)
+            .clamp(min_spacing, generator::max_vertical_spacing(&self.config));
+        let row_y = self.last_path.y - gap;
+
+        // Clamping to the screen can move it by half a platform
+        let reach = (generator::max_horizontal_reach(&self.config, gap) - platform_width / 2.)
+            .clamp(0., width / 2.);
+        let path_x = self.last_path.x + self.rng.get_inc(-reach, reach);
+        let path_x = if self.config.mode.wraps() {
+            path_x.rem_euclid(width)
+        } else {
+            path_x
+        }
+        .clamp(platform_width / 2., width - platform_width / 2.);
+
+        // Only dependable platforms on the path, the others are moved to the gap
+        let mut extra_kinds = Vec::new();
+        let mut kind = stage.platform_type(&mut self.rng);
+        if !matches!(kind, PlatformType::Green | PlatformType::White) {
+            extra_kinds.push(kind);
+            kind = PlatformType::Green;
+        }
+        for _ in 1..stage.platform_count(&mut self.rng) {
+            extra_kinds.push(stage.platform_type(&mut self.rng));
+        }
+
+        self.spawn_platform(maths::Point::new(path_x, row_y), kind);
+
+        // Each extra platform gets its own slice of the gap, so none share a height
+        let free_space = gap - 2. * min_spacing;
+        if free_space > 0. && !extra_kinds.is_empty() {
+            let slice = free_space / extra_kinds.len() as f64;
+
+            for (i, kind) in extra_kinds.into_iter().enumerate() {
+                let y =
+                    self.last_path.y - min_spacing - slice * i as f64 - self.rng.get_inc(0., slice);
+                let x = match kind {
+                    PlatformType::Red => self.away_from(path_x, platform_width),
+                    _ => self
+                        .rng
+                        .get_inc(platform_width / 2., width - platform_width / 2.),
+                };
+                self.spawn_platform(maths::Point::new(x, y), kind);
+            }
+        }
+
+        // Enemies sit in the gap between two rows, on the other side of the screen
+        if let Some(kind) = stage.spawn_enemy(&mut self.rng, gap) {
+            let pos =
+                maths::Point::new(self.away_from(path_x, enemy::ENEMY_SIZE), row_y + gap / 2.);
+            self.enemies.push(Enemy::new(self.next_enemy_id, pos, kind));
+            self.next_enemy_id += 1;
+        }
+
+        self.last_path = maths::Point::new(path_x, row_y);
+    }
+
+    fn spawn_platform(&mut self, pos: maths::Point, kind: platform::PlatformType) {
+        let size = maths::Vec2::new(self.config.platform_width, self.config.platform_height);
+
+        let platform = Platform::new(
+            self.next_platform_id,
+            maths::Rect::new_from_center(pos, size, 0.),
+            kind,
+        );
+        self.next_platform_id += 1;
+
+        if kind == platform::PlatformType::Green && self.rng.chance(POWER_UP_CHANCE) {
+            let power_up_kind = power_up::PowerUpType::random(&mut self.rng);
+            self.power_ups.push(PowerUp::on_platform(
+                self.next_power_up_id,
+                &platform,
+                power_up_kind,
+            ));
+            self.next_power_up_id += 1;
+        }
+
+        self.platforms.push(platform);
+    }
+
+    // Random x on the opposite half of the screen, for things that must stay off the path
+    fn away_from(&mut self, x: f64, width: f64) -> f64 {
+        let game_width = self.config.width;
+
+        (x + game_width / 2. + self.rng.get_inc(-game_width / 6., game_width / 6.))
+            .rem_euclid(game_width)
+            .clamp(width / 2., game_width - width / 2.)
+    }
+
+    // Why the current level can (or can't) be climbed
+    pub fn layout_report(&self) -> Result<generator::LayoutReport, generator::LayoutError> {
+        generator::validate(&self.config, &self.platforms)
+    }
+
+    // Played by that player during the next tick only
+    pub fn set_action(&mut self, player: usize, action: Action) {
+        self.players[player].action = action
+    }
+
+    // Over once every player is
+    pub fn is_over(&self) -> bool {
+        self.players.iter().all(Player::is_over)
+    }
+
+    // Give this to Game::with_seed to replay the same level
+    pub fn seed(&self) -> u64 {
+        self.rng.seed()
+    }
+}
+
+fn rebase_rect(rect: &mut maths::Rect, shift: f64) {
+    rect.set_center(rect.center() + maths::Vec2::new(0., shift));
+}
+
+#[cfg(test)]
+mod rebasing {
+    use super::*;
+
+    // Flat difficulty and no score limit, so only the coordinates change with the height
+    fn game_at(height: f64) -> Game {
+        let config = GameConfig {
+            difficulty: difficulty::DifficultyProfile {
+                stages: vec![difficulty::DifficultyStage::default()],
+            },
+            termination: termination::TerminationConfig {
+                stagnation_time: None,
+                max_ticks: None,
+                max_score: None,
+            },
+            ..Default::default()
+        };
+        let mut game = Game::with_seed(config, 0);
+
+        // Where everything would be after climbing that high without rebasing
+        game.rebase(-height);
+        game.origin = 0.;
+        for player in game.players.iter_mut() {
+            player.camera.origin = 0.;
+        }
+
+        game
+    }
+
+    #[test]
+    fn same_at_any_height() {
+        let mut low = game_at(1e3);
+        let mut high = game_at(1e8);
+        let config = low.config.clone();
+
+        for tick in 0..2_000 {
+            let action = match tick % 60 {
+                0..=14 => Action::Right,
+                30..=39 => Action::Left,
+                50 => Action::Shoot,
+                _ => Action::None,
+            };
+            for game in [&mut low, &mut high] {
+                game.set_action(0, action);
+                game.tick();
+            }
+
+            let (low, high) = (&low.players[0], &high.players[0]);
+            assert_eq!(low.is_over(), high.is_over());
+
+            let a = low.camera.normalize(&config, low.rect.center());
+            let b = high.camera.normalize(&config, high.rect.center());
+            assert!((a.x - b.x).abs() < 1e-9 && (a.y - b.y).abs() < 1e-9);
+            assert!((low.velocity.y - high.velocity.y).abs() < 1e-9);
+
+            assert!((high.camera.height() - low.camera.height() - (1e8 - 1e3)).abs() < 1e-6);
+            assert!(high.rect.center().y.abs() < 2. * REBASE_STEP);
+        }
+    }
+}
+
+#[cfg(test)]
+mod fixed_tick {
+    use super::*;
+
+    #[test]
+    fn leftover_time_is_kept() {
+        let mut game = Game::with_seed(GameConfig::default(), 0);
+
+        assert_eq!(game.accumulate(0.03), 0);
+        assert_eq!(game.accumulate(0.03), 1);
+        // 0.01 left from before
+        assert_eq!(game.accumulate(0.08), 1);
+        assert_eq!(game.accumulate(0.02), 1);
+
+        game.update(0.03);
+        assert_eq!(game.ticks, 0);
+        game.update(0.03);
+        assert_eq!(game.ticks, 1);
+    }
+
+    #[test]
+    fn lag_is_dropped() {
+        let mut game = Game::with_seed(GameConfig::default(), 0);
+
+        assert_eq!(game.accumulate(10.), MAX_TICKS_PER_UPDATE);
+        assert_eq!(game.accumulator, 0.);
+        assert_eq!(game.accumulate(0.04), 0);
+
+        game.update(10.);
+        assert_eq!(game.ticks, MAX_TICKS_PER_UPDATE as u64);
+    }
+}
+
+#[cfg(test)]
+mod snapshots {
+    use super::*;
+
+    // Everything that can be seen from the outside, exactly
+    fn state(game: &Game) -> Vec<f64> {
+        let players = game.players.iter().flat_map(|player| {
+            [
+                player.rect.center().x,
+                player.rect.center().y,
+                player.velocity.x,
+                player.velocity.y,
+                player.exact_score(),
+                player.is_over() as u8 as f64,
+            ]
+        });
+        let platforms = game.platforms.iter().flat_map(|platform| {
+            [
+                platform.id as f64,
+                platform.rect.center().x,
+                platform.rect.center().y,
+            ]
+        });
+        let enemies = game
+            .enemies
+            .iter()
+            .flat_map(|enemy| [enemy.rect.center().x, enemy.rect.center().y]);
+
+        players
+            .chain(platforms)
+            .chain(enemies)
+            .chain([game.ticks as f64])
+            .collect()
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut game = Game::with_seed(GameConfig::default(), 7);
+        let action = |tick: u64| Action::ALL[tick as usize / 5 % Action::COUNT];
+
+        for _ in 0..100 {
+            game.set_action(0, action(game.ticks));
+            game.tick();
+        }
+
+        let mut cloned = game.clone();
+        let mut loaded: Game =
+            serde_json::from_str(&serde_json::to_string(&game).unwrap()).unwrap();
+        assert_eq!(state(&loaded), state(&game));
+        assert_eq!(loaded.seed(), game.seed());
+
+        for _ in 0..300 {
+            for game in [&mut game, &mut cloned, &mut loaded] {
+                game.set_action(0, action(game.ticks));
+                game.tick();
+            }
+
+            assert_eq!(state(&cloned), state(&game));
+            assert_eq!(state(&loaded), state(&game));
+        }
+    }
+}
+
+#[cfg(test)]
+mod multiplayer {
+    use super::*;
+
+    #[test]
+    fn with_players() {
+        let game = Game::with_players(GameConfig::default(), 0, 3);
+        assert_eq!(game.players.len(), 3);
+
+        // There's always one
+        let game = Game::with_players(GameConfig::default(), 0, 0);
+        assert_eq!(game.players.len(), 1);
+    }
+
+    #[test]
+    fn fell_alone() {
+        let mut game = Game::with_players(GameConfig::default(), 0, 2);
+        game.players[1]
+            .rect
+            .set_center(maths::Point::new(270., 2_000.));
+        let velocity = game.players[1].velocity;
+
+        game.tick();
+
+        let fallen = &game.players[1];
+        assert_eq!(fallen.events(), [GameEvent::Died(event::DeathCause::Fell)]);
+        assert_eq!(fallen.terminal, Some(TerminalReason::Fell));
+        // Not moved after falling
+        assert_eq!(fallen.rect.center().y, 2_000.);
+        assert_eq!(fallen.velocity.y, velocity.y);
+
+        assert!(!game.players[0].is_over());
+        assert!(!game.is_over());
+    }
+
+    #[test]
+    fn scroll_range() {
+        let mut game = Game::with_players(GameConfig::default(), 0, 3);
+        game.players[0].camera.scroll = -100.;
+        game.players[1].camera.scroll = -500.;
+        // Ignored once over
+        game.players[2].camera.scroll = -2_000.;
+        game.players[2].terminal = Some(TerminalReason::Fell);
+
+        assert_eq!(game.scroll_range(), (-500., -100.));
+    }
+
+    // Tick until that player got the event, returns how many ticks it took
+    fn tick_until(game: &mut Game, player: usize, event: GameEvent) -> Option<u32> {
+        (1..=60).find(|_| {
+            game.tick();
+            game.players[player].events().contains(&event)
+        })
+    }
+
+    #[test]
+    fn broken_for_one() {
+        let mut game = Game::with_players(GameConfig::default(), 0, 2);
+        game.platforms = vec![testing::platform(
+            0,
+            270.,
+            560.,
+            platform::PlatformType::White,
+        )];
+        game.enemies.clear();
+        game.power_ups.clear();
+        // Falls on it after the other one
+        game.players[1]
+            .rect
+            .set_center(maths::Point::new(270., 300.));
+
+        assert!(tick_until(&mut game, 0, GameEvent::PlatformBroken(0)).is_some());
+        assert!(!game.players[0].level.is_solid(&game.platforms[0]));
+        assert!(game.players[1].level.is_solid(&game.platforms[0]));
 
-    pub fn score(&self) -> f32 {
-        return -self.scroll as f32;
+        assert!(tick_until(&mut game, 1, GameEvent::LandedOn(0)).is_some());
+        assert!(game.players[1].events().contains(&GameEvent::Jumped));
+        assert!(game.players[1].velocity.y < 0.);
     }
 }
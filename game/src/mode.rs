@@ -0,0 +1,132 @@
+// Rulesets on top of the simulation, they change how runs end and how they're scored
+// TerminationConfig still applies in every mode
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum GameMode {
+    // Climb as high as possible, the original game
+    #[default]
+    Endless,
+    // Climb as high as possible in that many seconds
+    TimeAttack {
+        duration: f64,
+    },
+    // Reach that height before the time limit, the time left is worth up to that height again
+    Race {
+        height: f64,
+        time_limit: f64,
+    },
+    // Endless, but the sides of the screen are walls
+    Hardcore,
+}
+
+impl GameMode {
+    // Whether the player goes through a side of the screen to come back on the other one
+    pub fn wraps(&self) -> bool {
+        !matches!(self, Self::Hardcore)
+    }
+
+    // Seconds before the run ends, for modes with a clock
+    pub fn time_left(&self, ticks: u64) -> Option<f64> {
+        let limit = match *self {
+            Self::TimeAttack { duration } => duration,
+            Self::Race { time_limit, .. } => time_limit,
+            Self::Endless | Self::Hardcore => return None,
+        };
+
+        // Dividing keeps whole seconds exact, summing TICK_DELTA_TIME would drift
+        Some((limit - ticks as f64 / crate::TICK_RATE as f64).max(0.))
+    }
+}
+
+#[cfg(test)]
+mod modes {
+    use super::*;
+    use crate::{Action, Game, GameConfig, GameEvent, TerminalReason};
+
+    fn game(mode: GameMode) -> Game {
+        Game::with_seed(
+            GameConfig {
+                mode,
+                ..Default::default()
+            },
+            0,
+        )
+    }
+
+    fn run(game: &mut Game, max_ticks: u64) {
+        while !game.is_over() && game.ticks < max_ticks {
+            game.tick();
+        }
+    }
+
+    #[test]
+    fn time_attack() {
+        let mut game = game(GameMode::TimeAttack { duration: 1. });
+        run(&mut game, 100);
+
+        assert_eq!(game.players[0].terminal, Some(TerminalReason::TimeUp));
+        assert_eq!(game.ticks, 20);
+    }
+
+    #[test]
+    fn race() {
+        let mut game = game(GameMode::Race {
+            height: 100.,
+            time_limit: 10.,
+        });
+        run(&mut game, 200);
+
+        assert_eq!(game.players[0].terminal, Some(TerminalReason::RaceFinished));
+        // Finished in about a second, so most of the bonus is left
+        assert!(game.players[0].exact_score() > 180.);
+    }
+
+    #[test]
+    fn time_left() {
+        let mode = GameMode::TimeAttack { duration: 30. };
+
+        assert_eq!(mode.time_left(0), Some(30.));
+        assert_eq!(mode.time_left(20 * 29), Some(1.));
+        assert_eq!(mode.time_left(20 * 31), Some(0.));
+        assert_eq!(GameMode::Endless.time_left(100), None);
+    }
+
+    #[test]
+    fn from_json() {
+        let config: GameConfig =
+            serde_json::from_str(r#"{ "mode": { "TimeAttack": { "duration": 30 } } }"#).unwrap();
+        assert_eq!(config.mode, GameMode::TimeAttack { duration: 30. });
+
+        let config: GameConfig = serde_json::from_str(r#"{ "mode": "Hardcore" }"#).unwrap();
+        assert_eq!(config.mode, GameMode::Hardcore);
+
+        let config: GameConfig =
+            serde_json::from_str(r#"{ "mode": { "Race": { "height": 5000, "time_limit": 60 } } }"#)
+                .unwrap();
+        assert_eq!(
+            config.mode,
+            GameMode::Race {
+                height: 5_000.,
+                time_limit: 60.
+            }
+        );
+
+        // Endless if it's missing
+        let config: GameConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.mode, GameMode::Endless);
+    }
+
+    #[test]
+    fn hardcore_walls() {
+        let mut game = game(GameMode::Hardcore);
+
+        for _ in 0..20 {
+            game.set_action(0, Action::Right);
+            game.tick();
+
+            let player = &game.players[0];
+            assert!(!player.events().contains(&GameEvent::Wrapped));
+            assert!(player.rect.center().x <= game.config.width - player.rect.width() / 2.);
+        }
+    }
+}
@@ -1,15 +1,91 @@
-pub const PLATFORM_BASE_WIDTH: f64 = 70.;
-pub const PLATFORM_BASE_HEIGHT: f64 = 20.;
+const MOVING_PLATFORM_SPEED: f64 = 100.;
 
-pub enum PlatformType {}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum PlatformType {
+    // Bounces the player
+    Green,
+    // Bounces the player and slides horizontally
+    Blue,
+    // Bounces the player once, then vanishes
+    White,
+    // Breaks when landed on, without bouncing the player
+    Cracked,
+    // Kills the player on contact
+    Red,
+}
 
-#[derive(Clone)]
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct Platform {
+    // Unique in its game, see GameEvent::LandedOn
+    pub id: u64,
+    #[serde(with = "crate::serde_maths::rect")]
     pub rect: maths::Rect,
+    pub kind: PlatformType,
+    // Horizontal speed, only used by blue platforms
+    pub velocity: f64,
 }
 
 impl Platform {
-    pub fn new(rect: impl Into<maths::Rect>) -> Self {
-        Self { rect: rect.into() }
+    pub fn new(id: u64, rect: impl Into<maths::Rect>, kind: PlatformType) -> Self {
+        Self {
+            id,
+            rect: rect.into(),
+            kind,
+            velocity: match kind {
+                PlatformType::Blue => MOVING_PLATFORM_SPEED,
+                _ => 0.,
+            },
+        }
+    }
+
+    pub fn update(&mut self, config: &crate::config::GameConfig, dt: f64) {
+        if self.kind == PlatformType::Blue {
+            self.rect
+                .set_center(self.rect.center() + maths::Vec2::new(self.velocity * dt, 0.));
+
+            // Bounce on the sides of the screen
+            if (self.rect.aa_topleft().x < 0. && self.velocity < 0.)
+                || (self.rect.aa_topright().x > config.width && self.velocity > 0.)
+            {
+                self.velocity = -self.velocity;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod platforms {
+    use super::*;
+    use crate::{
+        event::DeathCause,
+        testing::{platform, player_at},
+        GameConfig,
+    };
+
+    #[test]
+    fn blue_turns_at_the_side() {
+        let config = GameConfig::default();
+        let mut platform = platform(0, 500., 100., PlatformType::Blue);
+        assert_eq!(platform.velocity, MOVING_PLATFORM_SPEED);
+
+        platform.update(&config, 0.1);
+        assert!(platform.rect.center().x > 500.);
+        assert_eq!(platform.velocity, -MOVING_PLATFORM_SPEED);
+    }
+
+    #[test]
+    fn red_kills() {
+        let config = GameConfig::default();
+
+        // Landing on it
+        let platforms = [platform(0, 270., 500., PlatformType::Red)];
+        let mut player = player_at(270., 473., (0., 200.));
+        player.update(&config, &platforms, &[], &[], 0.05);
+        assert_eq!(player.death, Some(DeathCause::Hazard));
+
+        // Touching its side
+        let mut player = player_at(230., 500., (0., 0.));
+        player.update(&config, &platforms, &[], &[], 0.05);
+        assert_eq!(player.death, Some(DeathCause::Hazard));
     }
 }
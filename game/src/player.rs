@@ -1,70 +1,570 @@
-use crate::platform::Platform;
+use crate::{
+    config::GameConfig,
+    enemy::Enemy,
+    event::{DeathCause, GameEvent},
+    mode::GameMode,
+    platform::{Platform, PlatformType},
+    power_up::{ActivePowerUp, PowerUp},
+    projectile::Projectile,
+    termination::{StagnationTracker, TerminalReason, TerminationConfig},
+};
 
-const GRAVITY: f64 = 400.;
-const JUMP_HEIGHT: f64 = 575.;
-const SPEED: f64 = 400.;
+// How the horizontal input moves the player
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
+pub enum MovementModel {
+    // Full speed in the input's direction right away, like the original game
+    #[default]
+    Digital,
+    // The input accelerates the player, friction slows it down, up to GameConfig::speed
+    // The level generator accounts for the time it takes to get up to speed
+    Inertial {
+        acceleration: f64,
+        friction: f64,
+    },
+}
 
-const PLAYER_SIZE: f64 = 30.;
+impl MovementModel {
+    // Horizontal velocity after a tick with that input, in [-1, 1]
+    pub fn next_velocity(&self, velocity: f64, input: f64, top_speed: f64, dt: f64) -> f64 {
+        match *self {
+            Self::Digital => top_speed * input,
+            Self::Inertial {
+                acceleration,
+                friction,
+            } => ((velocity + input * acceleration * dt) * (1. - friction * dt).max(0.))
+                .clamp(-top_speed, top_speed),
+        }
+    }
+}
 
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct Player {
+    #[serde(with = "crate::serde_maths::rect")]
     pub rect: maths::Rect,
+    #[serde(with = "crate::serde_maths::vec2")]
     pub velocity: maths::Vec2,
-    pub current_direction: Option<bool>, // True -> Right as True == 1 == positive movement == Right
+    // Reset after each update
+    pub action: crate::Action,
+    // Why the player died, if it did
+    pub death: Option<DeathCause>,
+    // Flight power-up being used, if any
+    pub power_up: Option<ActivePowerUp>,
+    pub projectiles: Vec<Projectile>,
+    // Every player has its own camera
+    pub camera: crate::camera::Camera,
+    // Set when this player's run ended, it's not updated after that
+    pub terminal: Option<TerminalReason>,
+    pub stats: crate::stats::RunStats,
+    // What this player broke, killed and used, the other players don't see it
+    pub level: crate::level::LevelState,
+    // Side of the last horizontal input, kept when the input stops
+    facing_right: bool,
+    // Added to the score, for finishing a race early
+    bonus: f64,
     ignore_collisions_tag: bool,
+    shoot_timer: f64,
+    stagnation: StagnationTracker,
+    // Only the last tick's, they aren't part of the game state
+    #[serde(skip)]
+    events: Vec<GameEvent>,
 }
 
 impl Player {
-    pub fn new() -> Self {
+    pub fn new(config: &GameConfig) -> Self {
         Self {
             rect: maths::Rect::new_from_center(
-                (crate::GAME_WIDTH / 2., crate::GAME_HEIGHT / 2.),
-                (PLAYER_SIZE, PLAYER_SIZE),
+                (config.width / 2., config.height / 2.),
+                (config.player_size, config.player_size),
                 0.,
             ),
             velocity: maths::Vec2::ZERO,
-            current_direction: None,
+            action: crate::Action::None,
+            death: None,
+            power_up: None,
+            projectiles: Vec::new(),
+            camera: crate::camera::Camera::default(),
+            terminal: None,
+            stats: crate::stats::RunStats::new(config.height / 2.),
+            level: crate::level::LevelState::default(),
+            facing_right: true,
+            bonus: 0.,
             ignore_collisions_tag: false,
+            shoot_timer: 0.,
+            stagnation: StagnationTracker::default(),
+            events: Vec::new(),
         }
     }
 
-    pub fn direction(&self) -> i8 {
-        match self.current_direction {
-            Some(true) => 1,
-            Some(false) => -1,
-            None => 0,
+    pub fn score(&self) -> f32 {
+        self.exact_score() as f32
+    }
+
+    // The f32 score is too coarse to compare two ticks far up
+    pub fn exact_score(&self) -> f64 {
+        self.camera.height() + self.bonus
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.terminal.is_some()
+    }
+
+    // What happened to this player during the last tick
+    pub fn events(&self) -> &[GameEvent] {
+        &self.events
+    }
+
+    pub(crate) fn clear_events(&mut self) {
+        self.events.clear();
+    }
+
+    pub(crate) fn is_below_screen(&self, config: &GameConfig) -> bool {
+        self.camera.is_below(config, self.rect.center())
+    }
+
+    pub(crate) fn rebase(&mut self, shift: f64) {
+        crate::rebase_rect(&mut self.rect, shift);
+        for projectile in self.projectiles.iter_mut() {
+            crate::rebase_rect(&mut projectile.rect, shift);
+        }
+        self.camera.rebase(shift);
+        self.stats.rebase(shift);
+    }
+
+    pub(crate) fn update_camera(&mut self, config: &GameConfig, dt: f64) {
+        self.camera.update(config, self.rect.center(), dt);
+    }
+
+    // The mode's own ways to end a run, they come before TerminationConfig's
+    pub(crate) fn check_mode(&mut self, mode: &GameMode, ticks: u64) {
+        if self.terminal.is_some() {
+            return;
+        }
+
+        let time_left = mode.time_left(ticks);
+
+        if let GameMode::Race { height, time_limit } = *mode {
+            if self.camera.height() >= height {
+                self.bonus = height * time_left.unwrap_or_default() / time_limit;
+                self.terminal = Some(TerminalReason::RaceFinished);
+                return;
+            }
+        }
+
+        if time_left.is_some_and(|left| left <= 0.) {
+            self.terminal = Some(TerminalReason::TimeUp);
+        }
+    }
+
+    // The rules that can end a run while the player is still alive
+    pub(crate) fn check_termination(&mut self, rules: &TerminationConfig, ticks: u64, dt: f64) {
+        if self.terminal.is_some() {
+            return;
+        }
+
+        let score = self.score();
+
+        self.terminal = if rules.max_score.is_some_and(|max| score >= max) {
+            Some(TerminalReason::MaxScore)
+        } else if rules.max_ticks.is_some_and(|max| ticks >= max) {
+            Some(TerminalReason::MaxTicks)
+        } else if self.stagnation.update(rules, score, dt) {
+            Some(TerminalReason::Stagnated)
+        } else {
+            None
+        };
+    }
+
+    // Fires with the Shoot action, projectiles kill the first enemy they touch
+    pub(crate) fn update_projectiles(&mut self, config: &GameConfig, enemies: &[Enemy], dt: f64) {
+        let top = self.camera.top();
+        self.projectiles.retain(|projectile| {
+            !projectile.hit
+                && projectile.rect.center().y - top > -crate::projectile::PROJECTILE_SIZE
+        });
+
+        self.shoot_timer = (self.shoot_timer - dt).max(0.);
+
+        // Subtracting ticks leaves float crumbs, 0.3 - 6 * 0.05 isn't exactly 0
+        if self.action.is_shooting() && self.shoot_timer < 1e-9 {
+            let pos = maths::Point::new(self.rect.center().x, self.rect.aa_topleft().y);
+            self.projectiles.push(Projectile::new(pos));
+            self.shoot_timer = config.shoot_cooldown;
+            self.stats.shots += 1;
+        }
+
+        for projectile in self.projectiles.iter_mut() {
+            projectile.update(dt);
+
+            if let Some(enemy) = enemies.iter().find(|enemy| {
+                self.level.is_alive(enemy)
+                    && maths::collision::rect_rect_no_r(projectile.rect, enemy.rect)
+            }) {
+                self.level.kill(enemy);
+                projectile.hit = true;
+                self.events.push(GameEvent::EnemyKilled);
+            }
         }
     }
 
-    pub fn update(&mut self, platforms: &[Platform], dt: f64) {
+    pub fn direction(&self) -> i8 {
+        self.action.direction()
+    }
+
+    // The action is reset after each update, this stays until the player steers the other way
+    pub fn facing_right(&self) -> bool {
+        self.facing_right
+    }
+
+    pub fn update(
+        &mut self,
+        config: &GameConfig,
+        platforms: &[Platform],
+        enemies: &[Enemy],
+        power_ups: &[PowerUp],
+        dt: f64,
+    ) {
+        // Nothing happens to a dead player
+        if self.death.is_some() {
+            return;
+        }
+
+        let previous = self.rect;
         self.rect
             .set_center(self.rect.center() + self.velocity * dt);
 
-        self.update_collision(platforms);
+        if let Some(active) = self.power_up.as_mut() {
+            // Flying, nothing can touch the player
+            active.remaining -= dt;
+            let expired = active.remaining <= 0.;
+
+            if let Some((speed, _duration)) = active.kind.flight() {
+                self.velocity.y = -speed;
+            }
+
+            if expired {
+                self.power_up = None;
+            }
+        } else {
+            // A spring sits on top of its platform, don't let the platform override its bounce
+            if !self.update_power_up_collision(config, power_ups, previous) {
+                self.update_collision(config, platforms, previous);
+            }
+            if self.death.is_none() {
+                self.update_enemy_collision(config, enemies, previous);
+            }
+
+            // println!("{}", self.rect.center());
+
+            self.velocity.y += config.gravity * dt;
+        }
+        self.update_horizontal_velocity(config, dt);
+
+        match self.action.direction() {
+            1 => self.facing_right = true,
+            -1 => self.facing_right = false,
+            _ => (),
+        }
+        self.action = crate::Action::None;
 
-        // println!("{}", self.rect.center());
+        // Before wrapping, a wrap isn't a move across the screen
+        self.stats.update(
+            self.rect.center().y,
+            self.rect.center().x - previous.center().x,
+            &self.events,
+            dt,
+        );
 
-        self.velocity.y += GRAVITY * dt;
-        self.velocity.x = SPEED * self.direction() as f64;
+        // Died is the last event of a run
+        if self.death.is_some() {
+            return;
+        }
 
-        self.current_direction = None;
+        if !config.mode.wraps() {
+            // Stopped by the sides of the screen
+            let half_width = self.rect.width() / 2.;
+            let x = self
+                .rect
+                .center()
+                .x
+                .clamp(half_width, config.width - half_width);
 
-        if self.rect.center().x > crate::GAME_WIDTH {
+            if x != self.rect.center().x {
+                self.rect
+                    .set_center(maths::Vec2::new(x, self.rect.center().y));
+                self.velocity.x = 0.;
+            }
+        } else if self.rect.center().x > config.width {
             self.rect
-                .set_center(maths::Vec2::new(0., self.rect.center().y))
+                .set_center(maths::Vec2::new(0., self.rect.center().y));
+            self.events.push(GameEvent::Wrapped);
+            self.stats.wraps += 1;
         } else if self.rect.center().x < 0. {
             self.rect
-                .set_center(maths::Vec2::new(crate::GAME_WIDTH, self.rect.center().y))
+                .set_center(maths::Vec2::new(config.width, self.rect.center().y));
+            self.events.push(GameEvent::Wrapped);
+            self.stats.wraps += 1;
+        }
+    }
+
+    fn update_horizontal_velocity(&mut self, config: &GameConfig, dt: f64) {
+        self.velocity.x = config.movement.next_velocity(
+            self.velocity.x,
+            self.action.steering(),
+            config.speed,
+            dt,
+        );
+    }
+
+    // A player only dies once, the first cause is kept
+    pub fn die(&mut self, cause: DeathCause) {
+        if self.death.is_some() {
+            return;
+        }
+        self.death = Some(cause);
+        self.terminal.get_or_insert(cause.into());
+        self.events.push(GameEvent::Died(cause));
+    }
+
+    fn bounce(&mut self, strength: f64) {
+        self.velocity.y = -strength;
+        self.rect
+            .set_center(self.rect.center() - maths::Vec2::new(0., 1.));
+        self.events.push(GameEvent::Jumped);
+    }
+
+    // Returns if the player bounced on a power-up
+    // Both are checked along the move from previous, a fast fall can't skip a spring
+    fn update_power_up_collision(
+        &mut self,
+        config: &GameConfig,
+        power_ups: &[PowerUp],
+        previous: maths::Rect,
+    ) -> bool {
+        // Springs and trampolines only work when landed on, like platforms
+        if self.velocity.y > 0. {
+            let bouncy = || {
+                power_ups
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, power_up)| power_up.kind.bounce_multiplier().is_some())
+            };
+            // The sweep only sees the tops crossed during this move, not one the player is already in
+            let landed = self
+                .sweep_landing(previous, bouncy().map(|(i, power_up)| (i, power_up.rect)))
+                .or_else(|| {
+                    bouncy()
+                        .find(|(_, power_up)| {
+                            maths::collision::rect_rect_no_r(self.rect, power_up.rect)
+                        })
+                        .map(|(i, _)| (i, self.rect.center()))
+                });
+
+            if let Some((i, contact)) = landed {
+                let power_up = &power_ups[i];
+                if let Some(multiplier) = power_up.kind.bounce_multiplier() {
+                    self.rect.set_center(contact);
+                    self.level.use_power_up(power_up);
+                    self.bounce(config.jump_height * multiplier);
+                    return true;
+                }
+            }
+        }
+
+        // The others are picked up from any side
+        let picked_up = power_ups
+            .iter()
+            .filter(|power_up| self.level.is_available(power_up))
+            .filter_map(|power_up| {
+                let flight = power_up.kind.flight()?;
+                let (t, _) = self.sweep_contact(previous, power_up.rect)?;
+                Some((power_up, flight, t))
+            })
+            .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b));
+
+        if let Some((power_up, (_speed, duration), _)) = picked_up {
+            self.level.use_power_up(power_up);
+            self.power_up = Some(ActivePowerUp {
+                kind: power_up.kind,
+                remaining: duration,
+            });
+        }
+
+        false
+    }
+
+    // Landing on an enemy kills it, any other contact kills the player
+    // The first enemy touched while moving from previous counts, a fast player can't go through one
+    fn update_enemy_collision(
+        &mut self,
+        config: &GameConfig,
+        enemies: &[Enemy],
+        previous: maths::Rect,
+    ) {
+        let Some((enemy, contact)) = enemies
+            .iter()
+            .filter(|enemy| self.level.is_alive(enemy))
+            .filter_map(|enemy| {
+                let (t, contact) = self.sweep_contact(previous, enemy.rect)?;
+                Some((enemy, t, contact))
+            })
+            .min_by(|(_, a, _), (_, b, _)| a.total_cmp(b))
+            .map(|(enemy, _, contact)| (enemy, contact))
+        else {
+            return;
+        };
+
+        if !maths::collision::rect_rect_no_r(self.rect, enemy.rect) {
+            self.rect.set_center(contact);
+        }
+
+        if self.velocity.y > 0. && contact.y < enemy.rect.aa_topleft().y {
+            self.level.kill(enemy);
+            self.events.push(GameEvent::EnemyKilled);
+            self.bounce(config.jump_height);
+        } else {
+            self.die(DeathCause::Enemy);
+        }
+    }
+
+    // How far along the move from previous the player first touched the rect, in [0, 1],
+    // and where its center was then
+    // The rect is grown by the player's size, so only the center has to be cast through it
+    fn sweep_contact(
+        &self,
+        previous: maths::Rect,
+        rect: maths::Rect,
+    ) -> Option<(f64, maths::Point)> {
+        let from = previous.center();
+        let movement =
+            maths::Vec2::new(self.rect.center().x - from.x, self.rect.center().y - from.y);
+
+        // Not moving, only an overlap counts
+        if movement.x == 0. && movement.y == 0. {
+            return maths::collision::rect_rect_no_r(self.rect, rect).then_some((0., from));
+        }
+
+        let grown = maths::Rect::new_from_center(
+            rect.center(),
+            (
+                rect.width() + self.rect.width(),
+                rect.height() + self.rect.height(),
+            ),
+            0.,
+        );
+
+        crate::sensor::ray_rect(from, movement, grown, 1.).map(|t| {
+            (
+                t,
+                maths::Point::new(from.x + movement.x * t, from.y + movement.y * t),
+            )
+        })
+    }
+
+    // The first rect whose top the player's bottom crossed while moving from previous,
+    // so a fast fall can't skip a platform between two ticks
+    // Returns the index given with it and where the player was when it touched it
+    fn sweep_landing(
+        &self,
+        previous: maths::Rect,
+        rects: impl Iterator<Item = (usize, maths::Rect)>,
+    ) -> Option<(usize, maths::Point)> {
+        let from = previous.aa_botleft().y;
+        let to = self.rect.aa_botleft().y;
+        if to <= from {
+            return None;
+        }
+
+        let half_width = self.rect.width() / 2.;
+        let dx = self.rect.center().x - previous.center().x;
+
+        rects
+            .filter_map(|(i, rect)| {
+                let top = rect.aa_topleft().y;
+                if top < from || top > to {
+                    return None;
+                }
+
+                // Where the player was when it reached that height
+                let t = (top - from) / (to - from);
+                let x = previous.center().x + dx * t;
+                if x + half_width < rect.aa_topleft().x || x - half_width > rect.aa_topright().x {
+                    return None;
+                }
+
+                Some((i, t, maths::Point::new(x, top - self.rect.height() / 2.)))
+            })
+            .min_by(|(_, a, _), (_, b, _)| a.total_cmp(b))
+            .map(|(i, _, contact)| (i, contact))
+    }
+
+    fn land_on(&mut self, config: &GameConfig, platform: &Platform) {
+        self.events.push(GameEvent::LandedOn(platform.id));
+
+        match platform.kind {
+            PlatformType::Green | PlatformType::Blue => self.bounce(config.jump_height),
+            PlatformType::White => {
+                self.bounce(config.jump_height);
+                self.level.remove(platform);
+                self.events.push(GameEvent::PlatformBroken(platform.id));
+            }
+            PlatformType::Cracked => {
+                // Falls through
+                self.level.crack(platform);
+                self.events.push(GameEvent::PlatformBroken(platform.id));
+            }
+            // Landing on it is a contact like any other
+            PlatformType::Red => self.die(DeathCause::Hazard),
         }
     }
 
     // Returns if the player collided this frame
-    fn update_collision(&mut self, platforms: &[Platform]) -> bool {
+    fn update_collision(
+        &mut self,
+        config: &GameConfig,
+        platforms: &[Platform],
+        previous: maths::Rect,
+    ) -> bool {
+        if self.velocity.y > 0. && !self.ignore_collisions_tag {
+            let solid = platforms
+                .iter()
+                .enumerate()
+                .filter(|(_, platform)| self.level.is_solid(platform))
+                .map(|(i, platform)| (i, platform.rect));
+
+            if let Some((i, contact)) = self.sweep_landing(previous, solid) {
+                self.rect.set_center(contact);
+                self.land_on(config, &platforms[i]);
+                return true;
+            }
+        }
+
+        // Hazards don't care about the direction, a fast player can't go through one either
+        if let Some((platform, contact)) = platforms
+            .iter()
+            .filter(|platform| self.level.is_solid(platform) && platform.kind == PlatformType::Red)
+            .filter_map(|platform| {
+                let (t, contact) = self.sweep_contact(previous, platform.rect)?;
+                Some((platform, t, contact))
+            })
+            .min_by(|(_, a, _), (_, b, _)| a.total_cmp(b))
+            .map(|(platform, _, contact)| (platform, contact))
+        {
+            if !maths::collision::rect_rect_no_r(self.rect, platform.rect) {
+                self.rect.set_center(contact);
+            }
+            self.die(DeathCause::Hazard);
+            return true;
+        }
+
         let mut collided_this_frame = false;
         for platform in platforms.iter() {
-            if !maths::collision::rect_rect_no_r(self.rect, platform.rect) {
+            if !self.level.is_solid(platform)
+                || !maths::collision::rect_rect_no_r(self.rect, platform.rect)
+            {
                 continue;
             }
             collided_this_frame = true;
+
             // If player entered the platform from below, ingore all collision until out
             if self.ignore_collisions_tag {
                 break;
@@ -88,10 +588,7 @@ impl Player {
                 // ));
             } else if self.velocity.y > 0. && !self.ignore_collisions_tag {
                 // println!("Collision from above");
-
-                self.velocity.y = -JUMP_HEIGHT;
-                self.rect
-                    .set_center(self.rect.center() - maths::Vec2::new(0., 1.));
+                self.land_on(config, platform);
             } else if self.velocity.y < 0. && !self.ignore_collisions_tag {
                 // println!("Collision from below");
                 self.ignore_collisions_tag = true;
@@ -115,3 +612,284 @@ impl Player {
         collided_this_frame
     }
 }
+
+#[cfg(test)]
+mod player_events {
+    use super::*;
+    use crate::{
+        enemy::EnemyType,
+        testing::{platform, player_at},
+    };
+
+    fn land_on(kind: PlatformType) -> (Player, Platform) {
+        let config = GameConfig::default();
+        let platforms = [platform(0, 270., 500., kind)];
+        let mut player = player_at(270., 470., (0., 200.));
+
+        player.update(&config, &platforms, &[], &[], 0.05);
+
+        let [platform] = platforms;
+        (player, platform)
+    }
+
+    #[test]
+    fn landing() {
+        let (player, _) = land_on(PlatformType::Green);
+
+        assert_eq!(player.events(), [GameEvent::LandedOn(0), GameEvent::Jumped]);
+    }
+
+    #[test]
+    fn breaking() {
+        let (player, platform) = land_on(PlatformType::White);
+        assert_eq!(
+            player.events(),
+            [
+                GameEvent::LandedOn(0),
+                GameEvent::Jumped,
+                GameEvent::PlatformBroken(0)
+            ]
+        );
+        assert!(player.level.is_removed(&platform));
+
+        // Falls through a cracked one
+        let (player, _) = land_on(PlatformType::Cracked);
+        assert_eq!(
+            player.events(),
+            [GameEvent::LandedOn(0), GameEvent::PlatformBroken(0)]
+        );
+        assert!(player.velocity.y > 0.);
+    }
+
+    #[test]
+    fn wrapping() {
+        let config = GameConfig::default();
+        let mut player = player_at(535., 300., (400., 0.));
+
+        player.update(&config, &[], &[], &[], 0.05);
+        assert_eq!(player.events(), [GameEvent::Wrapped]);
+        assert_eq!(player.rect.center().x, 0.);
+    }
+
+    #[test]
+    fn nothing_after_death() {
+        let config = GameConfig::default();
+        let platforms = [platform(0, 270., 500., PlatformType::Red)];
+        // Right under the red platform, it would be stomped
+        let enemies = [Enemy::new(
+            0,
+            maths::Point::new(270., 505.),
+            EnemyType::Static,
+        )];
+        let mut player = player_at(270., 473., (0., 200.));
+
+        player.update(&config, &platforms, &enemies, &[], 0.05);
+        let events = [GameEvent::LandedOn(0), GameEvent::Died(DeathCause::Hazard)];
+        assert_eq!(player.events(), events);
+        assert!(player.level.is_alive(&enemies[0]));
+
+        let position = player.rect.center();
+        player.update(&config, &platforms, &enemies, &[], 0.05);
+        assert_eq!(player.events(), events);
+        assert_eq!(player.rect.center().y, position.y);
+    }
+}
+
+#[cfg(test)]
+mod sweeps {
+    use super::*;
+    use crate::{enemy::EnemyType, power_up::PowerUpType, testing::player_at};
+
+    // Thinner than what the player moves in a tick
+    fn thin_platform() -> Platform {
+        Platform::new(
+            0,
+            maths::Rect::new_from_center(
+                maths::Point::new(270., 500.),
+                maths::Vec2::new(70., 4.),
+                0.,
+            ),
+            PlatformType::Green,
+        )
+    }
+
+    // Where the player was when it landed, None if it went through
+    fn land(dt: f64) -> Option<f64> {
+        let config = GameConfig::default();
+        let platforms = [thin_platform()];
+        let mut player = player_at(270., 400., (0., 3_000.));
+
+        while player.rect.center().y < 600. {
+            player.update(&config, &platforms, &[], &[], dt);
+            if player.events().contains(&GameEvent::LandedOn(0)) {
+                return Some(player.rect.center().y);
+            }
+        }
+
+        None
+    }
+
+    #[test]
+    fn fast_fall() {
+        // Top of the platform, minus half the player, minus the bounce nudge
+        assert_eq!(land(0.05), Some(482.));
+    }
+
+    #[test]
+    fn same_at_any_tick_size() {
+        assert_eq!(land(0.05), land(0.01));
+    }
+
+    #[test]
+    fn fast_stomp() {
+        let config = GameConfig::default();
+        let enemies = [Enemy::new(
+            0,
+            maths::Point::new(270., 500.),
+            EnemyType::Static,
+        )];
+        let mut player = player_at(270., 400., (0., 3_000.));
+
+        player.update(&config, &[], &enemies, &[], 0.05);
+        assert!(!player.level.is_alive(&enemies[0]));
+        assert!(player.death.is_none());
+        assert_eq!(player.events(), [GameEvent::EnemyKilled, GameEvent::Jumped]);
+        // Touched the top of the enemy
+        assert!((player.rect.center().y - 459.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fast_hazard() {
+        let config = GameConfig::default();
+        let platforms = [crate::testing::platform(0, 270., 300., PlatformType::Red)];
+        let mut player = player_at(270., 400., (0., -3_000.));
+
+        player.update(&config, &platforms, &[], &[], 0.05);
+        assert_eq!(player.death, Some(DeathCause::Hazard));
+        // Stopped under the platform
+        assert_eq!(player.rect.center().y, 325.);
+    }
+
+    #[test]
+    fn fast_spring() {
+        let config = GameConfig::default();
+        let platforms = [crate::testing::platform(0, 270., 500., PlatformType::Green)];
+        let power_ups = [PowerUp::on_platform(0, &platforms[0], PowerUpType::Spring)];
+        // Ends the tick past the spring, only the platform would be overlapped
+        let mut player = player_at(270., 450., (0., 1_200.));
+
+        player.update(&config, &platforms, &[], &power_ups, 0.05);
+        assert_eq!(player.events(), [GameEvent::Jumped]);
+        assert!(player.level.is_used(&power_ups[0]));
+
+        let multiplier = PowerUpType::Spring.bounce_multiplier().unwrap();
+        assert_eq!(
+            player.velocity.y,
+            -config.jump_height * multiplier + config.gravity * 0.05
+        );
+        // Top of the spring, minus half the player, minus the bounce nudge
+        assert_eq!(player.rect.center().y, 459.);
+    }
+
+    #[test]
+    fn fast_pickup() {
+        let config = GameConfig::default();
+        let power_ups = [PowerUp::new(
+            0,
+            maths::Point::new(270., 480.),
+            PowerUpType::Propeller,
+        )];
+        let mut player = player_at(270., 400., (0., 3_000.));
+
+        player.update(&config, &[], &[], &power_ups, 0.05);
+        assert!(player.level.is_used(&power_ups[0]));
+        assert_eq!(
+            player.power_up.map(|active| active.kind),
+            Some(PowerUpType::Propeller)
+        );
+    }
+}
+
+#[cfg(test)]
+mod movement {
+    use super::*;
+    use crate::{testing::player_at, Action};
+
+    fn inertial(acceleration: f64, friction: f64) -> GameConfig {
+        GameConfig {
+            movement: MovementModel::Inertial {
+                acceleration,
+                friction,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn digital() {
+        let config = GameConfig::default();
+        let mut player = player_at(270., 480., (0., 0.));
+
+        player.action = Action::Steer(0.5);
+        player.update_horizontal_velocity(&config, 0.05);
+        assert_eq!(player.velocity.x, config.speed / 2.);
+
+        player.action = Action::Left;
+        player.update_horizontal_velocity(&config, 0.05);
+        assert_eq!(player.velocity.x, -config.speed);
+
+        // Stops right away
+        player.action = Action::None;
+        player.update_horizontal_velocity(&config, 0.05);
+        assert_eq!(player.velocity.x, 0.);
+    }
+
+    #[test]
+    fn facing() {
+        let config = GameConfig::default();
+        let mut player = player_at(270., 300., (0., 0.));
+        assert!(player.facing_right());
+
+        for (action, facing_right) in [
+            (Action::Left, false),
+            (Action::None, false),
+            (Action::Shoot, false),
+            (Action::Steer(0.3), true),
+            (Action::None, true),
+        ] {
+            player.action = action;
+            player.update(&config, &[], &[], &[], 0.05);
+            assert_eq!(player.facing_right(), facing_right);
+        }
+    }
+
+    #[test]
+    fn top_speed() {
+        let config = inertial(2_000., 0.);
+        let mut player = player_at(270., 480., (0., 0.));
+
+        player.action = Action::Right;
+        player.update_horizontal_velocity(&config, 0.05);
+        assert_eq!(player.velocity.x, 100.);
+
+        for _ in 0..20 {
+            player.action = Action::Right;
+            player.update_horizontal_velocity(&config, 0.05);
+        }
+        assert_eq!(player.velocity.x, config.speed);
+    }
+
+    #[test]
+    fn friction() {
+        let config = inertial(2_000., 2.);
+        let mut player = player_at(270., 480., (400., 0.));
+
+        player.update_horizontal_velocity(&config, 0.05);
+        assert!((player.velocity.x - 360.).abs() < 1e-9);
+
+        // Never pushes the other way
+        let config = inertial(2_000., 100.);
+        player.update_horizontal_velocity(&config, 0.05);
+        assert_eq!(player.velocity.x, 0.);
+    }
+}
@@ -0,0 +1,193 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum PowerUpType {
+    // Sits on a platform, bounces the player higher than the platform would
+    Spring,
+    // Same as a spring, but stronger
+    Trampoline,
+    // Picked up, makes the player fly up for a while
+    // Nothing can touch a flying player, enemies and red platforms included
+    Propeller,
+    // Same as a propeller, but faster and longer
+    Jetpack,
+}
+
+impl PowerUpType {
+    pub fn random(rng: &mut crate::rng::Rng) -> Self {
+        let roll = rng.next_f64();
+
+        if roll < 0.5 {
+            Self::Spring
+        } else if roll < 0.75 {
+            Self::Trampoline
+        } else if roll < 0.92 {
+            Self::Propeller
+        } else {
+            Self::Jetpack
+        }
+    }
+
+    pub fn size(&self) -> maths::Vec2 {
+        match self {
+            Self::Spring => maths::Vec2::new(20., 15.),
+            Self::Trampoline => maths::Vec2::new(50., 12.),
+            Self::Propeller => maths::Vec2::new(30., 20.),
+            Self::Jetpack => maths::Vec2::new(25., 35.),
+        }
+    }
+
+    // Springs and trampolines stay on their platform after being used
+    pub fn is_attached(&self) -> bool {
+        matches!(self, Self::Spring | Self::Trampoline)
+    }
+
+    // Multiplier applied to the player's jump when landing on it
+    pub fn bounce_multiplier(&self) -> Option<f64> {
+        match self {
+            Self::Spring => Some(1.6),
+            Self::Trampoline => Some(2.2),
+            _ => None,
+        }
+    }
+
+    // Upward speed and duration of the flight it gives
+    pub fn flight(&self) -> Option<(f64, f64)> {
+        match self {
+            Self::Propeller => Some((600., 2.5)),
+            Self::Jetpack => Some((900., 3.5)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct PowerUp {
+    // Unique in its game
+    pub id: u64,
+    #[serde(with = "crate::serde_maths::rect")]
+    pub rect: maths::Rect,
+    pub kind: PowerUpType,
+}
+
+impl PowerUp {
+    pub fn new(id: u64, center: impl Into<maths::Point>, kind: PowerUpType) -> Self {
+        Self {
+            id,
+            rect: maths::Rect::new_from_center(center.into(), kind.size(), 0.),
+            kind,
+        }
+    }
+
+    // Placed on top of the given platform
+    pub fn on_platform(id: u64, platform: &crate::platform::Platform, kind: PowerUpType) -> Self {
+        let center = maths::Point::new(
+            platform.rect.center().x,
+            platform.rect.aa_topleft().y - kind.size().y / 2.,
+        );
+
+        Self::new(id, center, kind)
+    }
+}
+
+// A flight power-up currently carried by the player
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct ActivePowerUp {
+    pub kind: PowerUpType,
+    pub remaining: f64,
+}
+
+#[cfg(test)]
+mod power_ups {
+    use super::*;
+    use crate::{
+        enemy::{Enemy, EnemyType},
+        level::LevelState,
+        platform::PlatformType,
+        testing::{platform, player_at},
+        GameConfig,
+    };
+
+    fn bounce(kind: PowerUpType) {
+        let config = GameConfig::default();
+        let power_ups = [PowerUp::new(0, maths::Point::new(270., 500.), kind)];
+        let mut level = LevelState::default();
+
+        // Used ones still work
+        for _ in 0..2 {
+            let mut player = player_at(270., 480., (0., 200.));
+            player.level = level;
+            player.update(&config, &[], &[], &power_ups, 0.05);
+
+            let expected = -(config.jump_height * kind.bounce_multiplier().unwrap());
+            assert_eq!(player.velocity.y, expected + config.gravity * 0.05);
+            assert!(player.level.is_used(&power_ups[0]));
+            level = player.level;
+        }
+
+        // Not when going up through it
+        let mut player = player_at(270., 510., (0., -200.));
+        player.update(&config, &[], &[], &power_ups, 0.05);
+        assert_eq!(player.velocity.y, -200. + config.gravity * 0.05);
+    }
+
+    #[test]
+    fn spring() {
+        bounce(PowerUpType::Spring);
+    }
+
+    #[test]
+    fn trampoline() {
+        bounce(PowerUpType::Trampoline);
+    }
+
+    #[test]
+    fn flight() {
+        let config = GameConfig::default();
+        let (speed, duration) = PowerUpType::Propeller.flight().unwrap();
+        let power_ups = [PowerUp::new(
+            0,
+            maths::Point::new(270., 480.),
+            PowerUpType::Propeller,
+        )];
+
+        let mut player = player_at(270., 480., (0., 0.));
+        player.update(&config, &[], &[], &power_ups, 0.05);
+        assert!(player.level.is_used(&power_ups[0]));
+        assert!(!player.level.is_available(&power_ups[0]));
+        assert_eq!(
+            player.power_up.map(|active| active.remaining),
+            Some(duration)
+        );
+
+        // Expires after its duration
+        player.power_up.as_mut().unwrap().remaining = 0.1;
+        for _ in 0..2 {
+            player.update(&config, &[], &[], &[], 0.05);
+            assert_eq!(player.velocity.y, -speed);
+        }
+        assert!(player.power_up.is_none());
+
+        player.update(&config, &[], &[], &[], 0.05);
+        assert_eq!(player.velocity.y, -speed + config.gravity * 0.05);
+    }
+
+    #[test]
+    fn invulnerable_while_flying() {
+        let config = GameConfig::default();
+        let platforms = [platform(0, 270., 480., PlatformType::Red)];
+        let enemies = [Enemy::new(
+            0,
+            maths::Point::new(270., 480.),
+            EnemyType::Static,
+        )];
+
+        let mut player = player_at(270., 480., (0., 0.));
+        player.power_up = Some(ActivePowerUp {
+            kind: PowerUpType::Jetpack,
+            remaining: 1.,
+        });
+        player.update(&config, &platforms, &enemies, &[], 0.05);
+
+        assert!(player.death.is_none());
+        assert!(player.level.is_alive(&enemies[0]));
+    }
+}
@@ -0,0 +1,119 @@
+pub const PROJECTILE_SIZE: f64 = 12.;
+
+const PROJECTILE_SPEED: f64 = 900.;
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct Projectile {
+    #[serde(with = "crate::serde_maths::rect")]
+    pub rect: maths::Rect,
+    #[serde(with = "crate::serde_maths::vec2")]
+    pub velocity: maths::Vec2,
+    // Set when it touched an enemy, it's removed at the next update
+    pub hit: bool,
+}
+
+impl Projectile {
+    // Fired straight up
+    pub fn new(center: impl Into<maths::Point>) -> Self {
+        Self {
+            rect: maths::Rect::new_from_center(
+                center.into(),
+                (PROJECTILE_SIZE, PROJECTILE_SIZE),
+                0.,
+            ),
+            velocity: maths::Vec2::new(0., -PROJECTILE_SPEED),
+            hit: false,
+        }
+    }
+
+    pub fn update(&mut self, dt: f64) {
+        self.rect
+            .set_center(self.rect.center() + self.velocity * dt);
+    }
+}
+
+#[cfg(test)]
+mod projectiles {
+    use super::*;
+    use crate::{
+        enemy::{Enemy, EnemyType},
+        testing::player_at,
+        Action, GameConfig, GameEvent,
+    };
+
+    #[test]
+    fn fire() {
+        let config = GameConfig::default();
+        let mut player = player_at(270., 480., (0., 0.));
+
+        player.action = Action::Shoot;
+        player.update_projectiles(&config, &[], 0.05);
+        assert_eq!(player.stats.shots, 1);
+        assert_eq!(player.projectiles.len(), 1);
+
+        // From the top of the player, then straight up
+        let projectile = &player.projectiles[0];
+        assert_eq!(projectile.rect.center().x, 270.);
+        assert_eq!(projectile.rect.center().y, 465. - PROJECTILE_SPEED * 0.05);
+    }
+
+    #[test]
+    fn cooldown() {
+        let config = GameConfig::default();
+        let mut player = player_at(270., 480., (0., 0.));
+        let mut fired = Vec::new();
+
+        for tick in 0..13 {
+            let shots = player.stats.shots;
+            player.action = Action::Shoot;
+            player.update_projectiles(&config, &[], crate::TICK_DELTA_TIME);
+
+            if player.stats.shots > shots {
+                fired.push(tick);
+            }
+        }
+
+        // 0.3s is exactly 6 ticks
+        assert_eq!(fired, [0, 6, 12]);
+    }
+
+    #[test]
+    fn off_screen() {
+        let config = GameConfig::default();
+        let mut player = player_at(270., 480., (0., 0.));
+
+        player.action = Action::Shoot;
+        player.update_projectiles(&config, &[], 0.05);
+        player.action = Action::None;
+
+        for _ in 0..20 {
+            player.update_projectiles(&config, &[], 0.05);
+        }
+        assert!(player.projectiles.is_empty());
+    }
+
+    #[test]
+    fn kill() {
+        let config = GameConfig::default();
+        let enemies = [
+            Enemy::new(0, maths::Point::new(270., 400.), EnemyType::Static),
+            // Behind the first one
+            Enemy::new(1, maths::Point::new(270., 350.), EnemyType::Static),
+        ];
+        let mut player = player_at(270., 480., (0., 0.));
+
+        player.action = Action::Shoot;
+        player.update_projectiles(&config, &enemies, 0.05);
+        assert!(!player.level.is_alive(&enemies[0]));
+        assert_eq!(player.events(), [GameEvent::EnemyKilled]);
+
+        // Used up
+        player.action = Action::None;
+        player.update_projectiles(&config, &enemies, 0.05);
+        assert!(player.projectiles.is_empty());
+        assert!(player.level.is_alive(&enemies[1]));
+
+        player.update(&config, &[], &[], &[], 0.05);
+        assert_eq!(player.stats.kills, 1);
+    }
+}
@@ -0,0 +1,274 @@
+// Geometry queries for feature extractors, they all take the screen wrap into account
+use crate::{
+    enemy::{Enemy, EnemyType},
+    generator,
+    platform::{Platform, PlatformType},
+    power_up::{PowerUp, PowerUpType},
+    Game, GameConfig,
+};
+
+// Shortest move from `from` to `to`, going through a side of the screen if that's shorter
+pub fn wrapped_offset(config: &GameConfig, from: maths::Point, to: maths::Point) -> maths::Vec2 {
+    if !config.mode.wraps() {
+        return maths::Vec2::new(to.x - from.x, to.y - from.y);
+    }
+
+    let dx = (to.x - from.x).rem_euclid(config.width);
+    let dx = if dx > config.width / 2. {
+        dx - config.width
+    } else {
+        dx
+    };
+
+    maths::Vec2::new(dx, to.y - from.y)
+}
+
+pub fn wrapped_point_distance(config: &GameConfig, from: maths::Point, to: maths::Point) -> f64 {
+    let offset = wrapped_offset(config, from, to);
+    (offset.x * offset.x + offset.y * offset.y).sqrt()
+}
+
+// Anything the player can interact with
+#[derive(Clone, Copy)]
+pub enum Entity<'a> {
+    Platform(&'a Platform),
+    Enemy(&'a Enemy),
+    PowerUp(&'a PowerUp),
+}
+
+// What an entity is, without borrowing it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntityKind {
+    Platform(PlatformType),
+    Enemy(EnemyType),
+    PowerUp(PowerUpType),
+}
+
+impl Entity<'_> {
+    pub fn rect(&self) -> maths::Rect {
+        match self {
+            Self::Platform(platform) => platform.rect,
+            Self::Enemy(enemy) => enemy.rect,
+            Self::PowerUp(power_up) => power_up.rect,
+        }
+    }
+
+    pub fn kind(&self) -> EntityKind {
+        match self {
+            Self::Platform(platform) => EntityKind::Platform(platform.kind),
+            Self::Enemy(enemy) => EntityKind::Enemy(enemy.kind),
+            Self::PowerUp(power_up) => EntityKind::PowerUp(power_up.kind),
+        }
+    }
+}
+
+impl Game {
+    // Platforms still solid for the player, nearest first
+    pub fn nearest_platforms(&self, player: usize, n: usize) -> Vec<&Platform> {
+        let player = &self.players[player];
+        let center = player.rect.center();
+
+        let mut platforms = self
+            .platforms
+            .iter()
+            .filter(|platform| player.level.is_solid(platform))
+            .map(|platform| {
+                let distance = wrapped_point_distance(&self.config, center, platform.rect.center());
+                (distance, platform)
+            })
+            .collect::<Vec<_>>();
+        platforms.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        platforms
+            .into_iter()
+            .take(n)
+            .map(|(_, platform)| platform)
+            .collect()
+    }
+
+    // Platforms at least partly on that player's screen
+    pub fn platforms_in_view(&self, player: usize) -> Vec<&Platform> {
+        let camera = &self.players[player].camera;
+        let (top, bottom) = (camera.top(), camera.bottom(&self.config));
+
+        self.platforms
+            .iter()
+            .filter(|platform| {
+                platform.rect.aa_botleft().y >= top && platform.rect.aa_topleft().y <= bottom
+            })
+            .collect()
+    }
+
+    // Entities with their center above the player's, nearest first
+    pub fn entities_above(&self, player: usize) -> Vec<Entity<'_>> {
+        self.entities_by_height(player, true)
+    }
+
+    // Entities with their center below (or at the height of) the player's, nearest first
+    pub fn entities_below(&self, player: usize) -> Vec<Entity<'_>> {
+        self.entities_by_height(player, false)
+    }
+
+    // Lowest platform above the player it can land on with a bounce from where it is
+    pub fn next_reachable_platform(&self, player: usize) -> Option<&Platform> {
+        let player = &self.players[player];
+        let feet = player.rect.aa_botleft().y;
+        // Seen as a platform the player would stand on, like the generator does
+        let from = maths::Point::new(
+            player.rect.center().x,
+            feet + self.config.platform_height / 2.,
+        );
+
+        self.platforms
+            .iter()
+            .filter(|platform| {
+                generator::is_dependable(platform)
+                    && player.level.is_solid(platform)
+                    && platform.rect.aa_topleft().y < feet
+            })
+            .filter(|platform| generator::jump(&self.config, from, platform.rect.center()).is_ok())
+            .max_by(|a, b| a.rect.center().y.total_cmp(&b.rect.center().y))
+    }
+
+    // Everything the player can still interact with
+    pub(crate) fn entities(&self, player: usize) -> impl Iterator<Item = Entity<'_>> {
+        let level = &self.players[player].level;
+
+        self.platforms
+            .iter()
+            .filter(|platform| level.is_solid(platform))
+            .map(Entity::Platform)
+            .chain(
+                self.enemies
+                    .iter()
+                    .filter(|enemy| level.is_alive(enemy))
+                    .map(Entity::Enemy),
+            )
+            .chain(
+                self.power_ups
+                    .iter()
+                    .filter(|power_up| level.is_available(power_up))
+                    .map(Entity::PowerUp),
+            )
+    }
+
+    fn entities_by_height(&self, player: usize, above: bool) -> Vec<Entity<'_>> {
+        let y = self.players[player].rect.center().y;
+
+        let mut entities = self
+            .entities(player)
+            .filter(|entity| (entity.rect().center().y < y) == above)
+            .collect::<Vec<_>>();
+        entities.sort_by(|a, b| {
+            (a.rect().center().y - y)
+                .abs()
+                .total_cmp(&(b.rect().center().y - y).abs())
+        });
+
+        entities
+    }
+}
+
+#[cfg(test)]
+mod queries {
+    use super::*;
+    use crate::testing::game_with;
+
+    fn ids(platforms: &[&Platform]) -> Vec<u64> {
+        platforms.iter().map(|platform| platform.id).collect()
+    }
+
+    #[test]
+    fn offset() {
+        let config = GameConfig::default();
+
+        let offset = wrapped_offset(
+            &config,
+            maths::Point::new(530., 0.),
+            maths::Point::new(10., 0.),
+        );
+        assert_eq!(offset.x, 20.);
+
+        let offset = wrapped_offset(
+            &config,
+            maths::Point::new(10., 0.),
+            maths::Point::new(530., 10.),
+        );
+        assert_eq!(offset.x, -20.);
+        assert_eq!(offset.y, 10.);
+
+        let offset = wrapped_offset(
+            &config,
+            maths::Point::new(100., 0.),
+            maths::Point::new(200., 0.),
+        );
+        assert_eq!(offset.x, 100.);
+    }
+
+    #[test]
+    fn nearest_sorted() {
+        let game = game_with(&[(270., 700.), (270., 520.), (270., 300.)]);
+
+        assert_eq!(ids(&game.nearest_platforms(0, 3)), vec![1, 2, 0]);
+        assert_eq!(ids(&game.nearest_platforms(0, 1)), vec![1]);
+        assert_eq!(ids(&game.nearest_platforms(0, 10)), vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn nearest_wraps() {
+        let mut game = game_with(&[(100., 480.), (530., 480.)]);
+        game.players[0]
+            .rect
+            .set_center(maths::Point::new(10., 480.));
+
+        assert_eq!(ids(&game.nearest_platforms(0, 2)), vec![1, 0]);
+    }
+
+    #[test]
+    fn nearest_skips_broken() {
+        let mut game = game_with(&[(270., 500.), (270., 600.)]);
+        game.players[0].level.crack(&game.platforms[0]);
+
+        assert_eq!(ids(&game.nearest_platforms(0, 2)), vec![1]);
+    }
+
+    #[test]
+    fn in_view() {
+        let mut game = game_with(&[(270., 500.), (270., -200.), (270., 1200.), (270., 955.)]);
+
+        assert_eq!(ids(&game.platforms_in_view(0)), vec![0, 3]);
+
+        game.players[0].camera.scroll = -300.;
+        assert_eq!(ids(&game.platforms_in_view(0)), vec![0, 1]);
+    }
+
+    #[test]
+    fn above_and_below() {
+        let mut game = game_with(&[(270., 300.), (270., 700.), (100., 600.)]);
+        game.enemies.push(Enemy::new(
+            0,
+            maths::Point::new(270., 100.),
+            EnemyType::Static,
+        ));
+
+        let above = game.entities_above(0);
+        assert_eq!(above.len(), 2);
+        assert!(matches!(above[0], Entity::Platform(platform) if platform.id == 0));
+        assert!(matches!(above[1], Entity::Enemy(_)));
+
+        let below = game.entities_below(0);
+        assert_eq!(below.len(), 2);
+        assert!(matches!(below[0], Entity::Platform(platform) if platform.id == 2));
+        assert!(matches!(below[1], Entity::Platform(platform) if platform.id == 1));
+    }
+
+    #[test]
+    fn next_reachable() {
+        // Too high, reachable, lowest reachable, below
+        let game = game_with(&[(270., 50.), (270., 300.), (270., 400.), (270., 700.)]);
+        assert_eq!(game.next_reachable_platform(0).map(|p| p.id), Some(2));
+
+        let game = game_with(&[(270., 50.), (270., 700.)]);
+        assert!(game.next_reachable_platform(0).is_none());
+    }
+}
@@ -0,0 +1,183 @@
+// A run is fully described by its seed, its config and the action played at each tick
+// Only single player games are recorded
+
+// Bump it when the format or the simulation changes in a way that breaks old replays
+pub const REPLAY_VERSION: u32 = 6;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub config: crate::GameConfig,
+    // One per tick
+    pub actions: Vec<crate::Action>,
+    // Score at the end of the recording
+    pub score: f32,
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(std::io::Error),
+    Format(serde_json::Error),
+    UnsupportedVersion(u32),
+    // The simulation didn't end with the recorded score
+    Mismatch { expected: f32, got: f32 },
+}
+
+impl std::fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Could not access the replay file: {e}"),
+            Self::Format(e) => write!(f, "Could not parse the replay: {e}"),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "Replay version {version} is not supported, expected {REPLAY_VERSION}"
+            ),
+            Self::Mismatch { expected, got } => write!(
+                f,
+                "Replay doesn't reproduce its run, expected a score of {expected} but got {got}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl Replay {
+    // Start recording a game, it must not have been updated yet
+    pub fn new(game: &crate::Game) -> Self {
+        Self {
+            version: REPLAY_VERSION,
+            seed: game.seed(),
+            config: game.config.clone(),
+            actions: Vec::new(),
+            score: game.players[0].score(),
+        }
+    }
+
+    // Use it instead of Game::tick to record the game
+    pub fn record(&mut self, game: &mut crate::Game) {
+        if game.is_over() {
+            return;
+        }
+        self.actions.push(game.players[0].action);
+        game.tick();
+        self.score = game.players[0].score();
+    }
+
+    // A new game at the start of the recording
+    pub fn game(&self) -> crate::Game {
+        crate::Game::with_seed(self.config.clone(), self.seed)
+    }
+
+    // Action to give to the game before its next tick, nothing once the recording is over
+    pub fn action_at(&self, tick: u64) -> crate::Action {
+        self.actions.get(tick as usize).copied().unwrap_or_default()
+    }
+
+    // Re-runs the whole recording
+    pub fn simulate(&self) -> crate::Game {
+        let mut game = self.game();
+
+        for action in self.actions.iter() {
+            game.set_action(0, *action);
+            game.tick();
+        }
+
+        game
+    }
+
+    pub fn verify(&self) -> Result<crate::Game, ReplayError> {
+        let game = self.simulate();
+
+        if game.players[0].score() != self.score {
+            return Err(ReplayError::Mismatch {
+                expected: self.score,
+                got: game.players[0].score(),
+            });
+        }
+
+        Ok(game)
+    }
+
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<(), ReplayError> {
+        let file = std::fs::File::create(path).map_err(ReplayError::Io)?;
+        serde_json::to_writer(std::io::BufWriter::new(file), self).map_err(ReplayError::Format)
+    }
+
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, ReplayError> {
+        let file = std::fs::File::open(path).map_err(ReplayError::Io)?;
+        let replay: Self =
+            serde_json::from_reader(std::io::BufReader::new(file)).map_err(ReplayError::Format)?;
+
+        if replay.version != REPLAY_VERSION {
+            return Err(ReplayError::UnsupportedVersion(replay.version));
+        }
+
+        Ok(replay)
+    }
+}
+
+#[cfg(test)]
+mod replays {
+    use super::*;
+    use crate::{Action, Game, GameConfig};
+
+    fn recorded() -> (Replay, Game) {
+        let mut game = Game::with_seed(GameConfig::default(), 3);
+        let mut replay = Replay::new(&game);
+
+        for tick in 0..300 {
+            game.set_action(0, Action::ALL[tick / 4 % Action::COUNT]);
+            replay.record(&mut game);
+        }
+
+        (replay, game)
+    }
+
+    fn path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("doodlai_{name}_{}.json", std::process::id()))
+    }
+
+    #[test]
+    fn round_trip() {
+        let (replay, game) = recorded();
+        assert_eq!(replay.actions.len() as u64, game.ticks);
+        assert_eq!(replay.verify().unwrap().ticks, game.ticks);
+
+        let path = path("round_trip");
+        replay.save(&path).unwrap();
+        let loaded = Replay::load(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded.actions, replay.actions);
+        let simulated = loaded.verify().unwrap();
+        assert_eq!(simulated.ticks, game.ticks);
+        assert_eq!(simulated.players[0].score(), game.players[0].score());
+    }
+
+    #[test]
+    fn mismatch() {
+        let (mut replay, _) = recorded();
+        replay.score += 1.;
+
+        assert!(matches!(replay.verify(), Err(ReplayError::Mismatch { .. })));
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let (mut replay, _) = recorded();
+        replay.version = REPLAY_VERSION + 1;
+
+        let path = path("unsupported_version");
+        replay.save(&path).unwrap();
+        let loaded = Replay::load(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            loaded,
+            Err(ReplayError::UnsupportedVersion(version)) if version == REPLAY_VERSION + 1
+        ));
+    }
+}
@@ -0,0 +1,100 @@
+// Small seedable generator owned by each game, so levels can be reproduced from a seed
+// SplitMix64, see https://prng.di.unimi.it/splitmix64.c
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Rng {
+    seed: u64,
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, state: seed }
+    }
+
+    // Used when the caller doesn't care about reproducibility
+    pub fn from_time() -> Self {
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default();
+        Self::new(seed)
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    // [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // Drop-in for random::get_inc
+    pub fn get_inc(&mut self, min: f64, max: f64) -> f64 {
+        min + (max - min) * self.next_f64()
+    }
+
+    pub fn chance(&mut self, probability: f64) -> bool {
+        self.next_f64() < probability
+    }
+}
+
+#[cfg(test)]
+mod rngs {
+    use super::*;
+    use crate::{Action, Game, GameConfig};
+
+    #[test]
+    fn same_sequence() {
+        let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+
+        assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+
+        let mut rng = Rng::new(7);
+        for _ in 0..100 {
+            let value = rng.get_inc(-5., 5.);
+            assert!((-5. ..=5.).contains(&value));
+        }
+    }
+
+    #[test]
+    fn same_seed_same_game() {
+        let mut a = Game::with_seed(GameConfig::default(), 42);
+        // The seed is enough to build the same level again
+        let mut b = Game::with_seed(GameConfig::default(), a.seed());
+        assert_eq!(a.seed(), 42);
+
+        for tick in 0..500 {
+            let action = Action::ALL[tick / 7 % Action::COUNT];
+            for game in [&mut a, &mut b] {
+                game.set_action(0, action);
+                game.tick();
+            }
+        }
+
+        let layout = |game: &Game| {
+            game.platforms
+                .iter()
+                .map(|platform| {
+                    let center = platform.rect.center();
+                    (platform.id, center.x, center.y, platform.kind)
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(layout(&a), layout(&b));
+        assert_eq!(a.players[0].score(), b.players[0].score());
+        assert_eq!(a.players[0].terminal, b.players[0].terminal);
+    }
+}
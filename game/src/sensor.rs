@@ -0,0 +1,165 @@
+// Raycasts from the player, they go through the sides of the screen like the player does
+use crate::{query::EntityKind, Game};
+
+// Angles are in degrees, 0 is straight up and 90 is to the right
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct SensorConfig {
+    pub angles: Vec<f64>,
+    pub max_distance: f64,
+}
+
+impl Default for SensorConfig {
+    fn default() -> Self {
+        Self {
+            // Every 45°
+            angles: (0..8).map(|i| i as f64 * 45.).collect(),
+            max_distance: 1000.,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    // max_distance if nothing was hit
+    pub distance: f64,
+    pub entity: Option<EntityKind>,
+}
+
+// Distance along the ray to the rect, if it's hit before max_distance
+pub(crate) fn ray_rect(
+    origin: maths::Point,
+    direction: maths::Vec2,
+    rect: maths::Rect,
+    max_distance: f64,
+) -> Option<f64> {
+    let mut enter = 0f64;
+    let mut exit = max_distance;
+
+    let min = rect.aa_topleft();
+    let max = maths::Point::new(rect.aa_topright().x, rect.aa_botleft().y);
+
+    for (origin, direction, min, max) in [
+        (origin.x, direction.x, min.x, max.x),
+        (origin.y, direction.y, min.y, max.y),
+    ] {
+        if direction.abs() < f64::EPSILON {
+            if origin < min || origin > max {
+                return None;
+            }
+            continue;
+        }
+
+        let a = (min - origin) / direction;
+        let b = (max - origin) / direction;
+        enter = enter.max(a.min(b));
+        exit = exit.min(a.max(b));
+
+        if enter > exit {
+            return None;
+        }
+    }
+
+    Some(enter)
+}
+
+impl Game {
+    pub fn raycast(&self, player: usize, angle: f64, max_distance: f64) -> RayHit {
+        let origin = self.players[player].rect.center();
+        let angle = angle.to_radians();
+        let direction = maths::Vec2::new(angle.sin(), -angle.cos());
+
+        let width = self.config.width;
+        let end_x = origin.x + direction.x * max_distance;
+        let (ray_left, ray_right) = (origin.x.min(end_x), origin.x.max(end_x));
+
+        let mut hit = RayHit {
+            distance: max_distance,
+            entity: None,
+        };
+
+        for entity in self.entities(player) {
+            let rect = entity.rect();
+
+            // Every copy of the entity the ray can cross through the wrap
+            let (first, last) = if self.config.mode.wraps() {
+                (
+                    ((ray_left - rect.aa_topright().x) / width).floor() as i64,
+                    ((ray_right - rect.aa_topleft().x) / width).ceil() as i64,
+                )
+            } else {
+                (0, 0)
+            };
+
+            for copy in first..=last {
+                let mut shifted = rect;
+                shifted.set_center(rect.center() + maths::Vec2::new(copy as f64 * width, 0.));
+
+                if let Some(distance) = ray_rect(origin, direction, shifted, hit.distance) {
+                    if distance < hit.distance || hit.entity.is_none() {
+                        hit = RayHit {
+                            distance,
+                            entity: Some(entity.kind()),
+                        };
+                    }
+                }
+            }
+        }
+
+        hit
+    }
+
+    // One hit per configured angle, in the same order
+    pub fn sensors(&self, player: usize, config: &SensorConfig) -> Vec<RayHit> {
+        config
+            .angles
+            .iter()
+            .map(|angle| self.raycast(player, *angle, config.max_distance))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod raycasts {
+    use super::*;
+    use crate::{platform::PlatformType, testing::game_with};
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-6, "{a} != {b}");
+    }
+
+    #[test]
+    fn straight_up() {
+        let game = game_with(&[(270., 300.), (270., 100.)]);
+
+        let hit = game.raycast(0, 0., 1000.);
+        assert_close(hit.distance, 170.);
+        assert_eq!(hit.entity, Some(EntityKind::Platform(PlatformType::Green)));
+    }
+
+    #[test]
+    fn through_the_side() {
+        let mut game = game_with(&[(500., 480.)]);
+        game.players[0]
+            .rect
+            .set_center(maths::Point::new(10., 480.));
+
+        // The right side of the platform is 5 px left of the screen's right edge
+        let hit = game.raycast(0, -90., 1000.);
+        assert_close(hit.distance, 15.);
+        assert_eq!(hit.entity, Some(EntityKind::Platform(PlatformType::Green)));
+    }
+
+    #[test]
+    fn miss() {
+        let game = game_with(&[(270., 300.)]);
+
+        let hit = game.raycast(0, 180., 400.);
+        assert_eq!(hit.distance, 400.);
+        assert_eq!(hit.entity, None);
+
+        let hits = game.sensors(0, &SensorConfig::default());
+        assert_eq!(hits.len(), 8);
+        assert!(hits[0].entity.is_some());
+    }
+}
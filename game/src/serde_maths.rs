@@ -0,0 +1,41 @@
+// maths types don't implement serde, use with #[serde(with = "crate::serde_maths::...")]
+
+pub mod vec2 {
+    pub fn serialize<S: serde::Serializer>(
+        vec: &maths::Vec2,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&[vec.x, vec.y], serializer)
+    }
+
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<maths::Vec2, D::Error> {
+        let [x, y]: [f64; 2] = serde::Deserialize::deserialize(deserializer)?;
+        Ok(maths::Vec2::new(x, y))
+    }
+}
+
+// Game rects are never rotated, only the center and size are kept
+// The rotation is dropped, a rotated rect comes back unrotated
+pub mod rect {
+    pub fn serialize<S: serde::Serializer>(
+        rect: &maths::Rect,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let center = rect.center();
+        let size = rect.size();
+        serde::Serialize::serialize(&([center.x, center.y], [size.x, size.y]), serializer)
+    }
+
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<maths::Rect, D::Error> {
+        let ([x, y], [w, h]): ([f64; 2], [f64; 2]) = serde::Deserialize::deserialize(deserializer)?;
+        Ok(maths::Rect::new_from_center(
+            maths::Point::new(x, y),
+            maths::Vec2::new(w, h),
+            0.,
+        ))
+    }
+}
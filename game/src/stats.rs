@@ -0,0 +1,172 @@
+// Kept up to date by the player each tick, so nothing has to be recomputed from the game state
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RunStats {
+    // Highest point reached, above the spawn
+    pub max_height: f64,
+    pub platforms_landed: u32,
+    pub unique_platforms_landed: u32,
+    // Seconds spent without landing or bouncing on anything
+    pub airtime: f64,
+    pub ticks_survived: u64,
+    pub wraps: u32,
+    // Sum of the horizontal moves, wrapping doesn't count as one
+    pub horizontal_distance: f64,
+    pub shots: u32,
+    pub kills: u32,
+    spawn_y: f64,
+    landed_on: std::collections::BTreeSet<u64>,
+}
+
+impl RunStats {
+    pub fn new(spawn_y: f64) -> Self {
+        Self {
+            max_height: 0.,
+            platforms_landed: 0,
+            unique_platforms_landed: 0,
+            airtime: 0.,
+            ticks_survived: 0,
+            wraps: 0,
+            horizontal_distance: 0.,
+            shots: 0,
+            kills: 0,
+            spawn_y,
+            landed_on: std::collections::BTreeSet::new(),
+        }
+    }
+
+    // Called once per tick with where the player went and what happened to it
+    pub(crate) fn update(
+        &mut self,
+        y: f64,
+        horizontal_move: f64,
+        events: &[crate::GameEvent],
+        dt: f64,
+    ) {
+        use crate::GameEvent;
+
+        self.ticks_survived += 1;
+        self.max_height = self.max_height.max(self.spawn_y - y);
+        self.horizontal_distance += horizontal_move.abs();
+
+        // Springs and stomps bounce without landing on a platform
+        if !events
+            .iter()
+            .any(|event| matches!(event, GameEvent::LandedOn(_) | GameEvent::Jumped))
+        {
+            self.airtime += dt;
+        }
+
+        for event in events {
+            match event {
+                GameEvent::LandedOn(id) => {
+                    self.platforms_landed += 1;
+                    if self.landed_on.insert(*id) {
+                        self.unique_platforms_landed += 1;
+                    }
+                }
+                GameEvent::EnemyKilled => self.kills += 1,
+                _ => (),
+            }
+        }
+    }
+
+    pub(crate) fn rebase(&mut self, shift: f64) {
+        self.spawn_y += shift;
+    }
+}
+
+#[cfg(test)]
+mod run_stats {
+    use super::*;
+    use crate::{platform::PlatformType, testing::player_at, GameConfig, GameEvent};
+
+    #[test]
+    fn max_height() {
+        let mut stats = RunStats::new(480.);
+
+        stats.update(400., 0., &[], 0.05);
+        assert_eq!(stats.max_height, 80.);
+        stats.update(450., 0., &[], 0.05);
+        assert_eq!(stats.max_height, 80.);
+
+        // Same height once everything moved down
+        stats.rebase(1_000.);
+        stats.update(1_350., 0., &[], 0.05);
+        assert_eq!(stats.max_height, 130.);
+        assert_eq!(stats.ticks_survived, 3);
+    }
+
+    #[test]
+    fn platforms() {
+        let mut stats = RunStats::new(480.);
+
+        stats.update(480., 0., &[GameEvent::LandedOn(1), GameEvent::Jumped], 0.05);
+        stats.update(480., 0., &[GameEvent::LandedOn(1)], 0.05);
+        stats.update(
+            480.,
+            0.,
+            &[GameEvent::LandedOn(2), GameEvent::EnemyKilled],
+            0.05,
+        );
+        assert_eq!(stats.platforms_landed, 3);
+        assert_eq!(stats.unique_platforms_landed, 2);
+        assert_eq!(stats.kills, 1);
+    }
+
+    #[test]
+    fn horizontal_distance() {
+        let mut stats = RunStats::new(480.);
+
+        stats.update(480., 10., &[], 0.05);
+        stats.update(480., -5., &[], 0.05);
+        assert_eq!(stats.horizontal_distance, 15.);
+    }
+
+    #[test]
+    fn airtime() {
+        let mut stats = RunStats::new(480.);
+
+        stats.update(480., 0., &[], 0.05);
+        stats.update(480., 0., &[GameEvent::Wrapped], 0.05);
+        assert_eq!(stats.airtime, 0.1);
+
+        // Not while landing or bouncing
+        stats.update(480., 0., &[GameEvent::LandedOn(1), GameEvent::Jumped], 0.05);
+        stats.update(480., 0., &[GameEvent::EnemyKilled, GameEvent::Jumped], 0.05);
+        stats.update(480., 0., &[GameEvent::LandedOn(2)], 0.05);
+        assert_eq!(stats.airtime, 0.1);
+    }
+
+    #[test]
+    fn airborne_player() {
+        let config = GameConfig::default();
+        let platforms = [crate::testing::platform(0, 270., 500., PlatformType::Green)];
+        let mut player = player_at(270., 300., (0., 0.));
+
+        // Every tick counts but the one it landed on
+        // Game::tick clears the events before each update
+        while !player.events().contains(&GameEvent::LandedOn(0)) {
+            player.clear_events();
+            player.update(&config, &platforms, &[], &[], 0.05);
+        }
+        let airborne = (player.stats.ticks_survived - 1) as f64 * 0.05;
+        assert!((player.stats.airtime - airborne).abs() < 1e-9);
+
+        // Going up after the bounce
+        player.clear_events();
+        player.update(&config, &platforms, &[], &[], 0.05);
+        assert!((player.stats.airtime - airborne - 0.05).abs() < 1e-9);
+    }
+
+    #[test]
+    fn wraps() {
+        let config = GameConfig::default();
+        let mut player = player_at(535., 300., (400., 0.));
+
+        player.update(&config, &[], &[], &[], 0.05);
+        assert_eq!(player.stats.wraps, 1);
+        // The move is counted, not the jump to the other side
+        assert_eq!(player.stats.horizontal_distance, 20.);
+    }
+}
@@ -0,0 +1,141 @@
+// Why a game ended, and the rules that end it besides the player dying
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum TerminalReason {
+    Fell,
+    KilledByEnemy,
+    // Touched a red platform
+    KilledByHazard,
+    // The score didn't change for TerminationConfig::stagnation_time
+    Stagnated,
+    MaxTicks,
+    MaxScore,
+    // Got above the end of the track, see TrackEnd::Finish
+    TrackCompleted,
+    // The clock of a GameMode::TimeAttack or GameMode::Race ran out
+    TimeUp,
+    // Reached the height of a GameMode::Race
+    RaceFinished,
+}
+
+impl From<crate::event::DeathCause> for TerminalReason {
+    fn from(cause: crate::event::DeathCause) -> Self {
+        use crate::event::DeathCause;
+
+        match cause {
+            DeathCause::Fell => Self::Fell,
+            DeathCause::Enemy => Self::KilledByEnemy,
+            DeathCause::Hazard => Self::KilledByHazard,
+        }
+    }
+}
+
+// None disables a rule
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct TerminationConfig {
+    // In seconds, a player stuck bouncing on the same platforms needs to be shot (ingame)
+    pub stagnation_time: Option<f64>,
+    pub max_ticks: Option<u64>,
+    pub max_score: Option<f32>,
+}
+
+impl Default for TerminationConfig {
+    fn default() -> Self {
+        Self {
+            stagnation_time: Some(10.),
+            max_ticks: None,
+            max_score: Some(100_000.),
+        }
+    }
+}
+
+// Score the stagnation rule compares against, saved every stagnation_time
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct StagnationTracker {
+    saved_score: f32,
+    elapsed: f64,
+}
+
+impl StagnationTracker {
+    // Returns true if the score didn't move since the last check
+    pub fn update(&mut self, config: &TerminationConfig, score: f32, dt: f64) -> bool {
+        let Some(stagnation_time) = config.stagnation_time else {
+            return false;
+        };
+
+        self.elapsed += dt;
+        if self.elapsed < stagnation_time {
+            return false;
+        }
+        self.elapsed -= stagnation_time;
+
+        let stagnated = score == self.saved_score;
+        self.saved_score = score;
+        stagnated
+    }
+}
+
+#[cfg(test)]
+mod rules {
+    use super::*;
+    use crate::testing::player_at;
+
+    fn rules(
+        stagnation_time: Option<f64>,
+        max_ticks: Option<u64>,
+        max_score: Option<f32>,
+    ) -> TerminationConfig {
+        TerminationConfig {
+            stagnation_time,
+            max_ticks,
+            max_score,
+        }
+    }
+
+    #[test]
+    fn stagnation() {
+        let config = rules(Some(1.), None, None);
+        let mut tracker = StagnationTracker::default();
+
+        // Only checked once a second
+        for score in [5., 10., 10.] {
+            assert!(!tracker.update(&config, score, 0.25));
+        }
+        assert!(!tracker.update(&config, 10., 0.25));
+        for _ in 0..3 {
+            assert!(!tracker.update(&config, 10., 0.25));
+        }
+        assert!(tracker.update(&config, 10., 0.25));
+
+        // Disabled
+        let mut tracker = StagnationTracker::default();
+        for _ in 0..100 {
+            assert!(!tracker.update(&rules(None, None, None), 0., 0.25));
+        }
+    }
+
+    #[test]
+    fn max_score() {
+        let config = rules(None, None, Some(100.));
+        let mut player = player_at(270., 480., (0., 0.));
+
+        player.check_termination(&config, 0, 0.05);
+        assert!(player.terminal.is_none());
+
+        player.camera.scroll = -200.;
+        player.check_termination(&config, 1, 0.05);
+        assert_eq!(player.terminal, Some(TerminalReason::MaxScore));
+    }
+
+    #[test]
+    fn max_ticks() {
+        let config = rules(None, Some(10), None);
+        let mut player = player_at(270., 480., (0., 0.));
+
+        player.check_termination(&config, 9, 0.05);
+        assert!(player.terminal.is_none());
+        player.check_termination(&config, 10, 0.05);
+        assert_eq!(player.terminal, Some(TerminalReason::MaxTicks));
+    }
+}
@@ -0,0 +1,39 @@
+// Fixtures shared by the test modules
+use crate::{
+    platform::{Platform, PlatformType},
+    player::Player,
+    Game, GameConfig,
+};
+
+// Default platform size, 70x20
+pub fn platform(id: u64, x: f64, y: f64, kind: PlatformType) -> Platform {
+    Platform::new(
+        id,
+        maths::Rect::new_from_center(maths::Point::new(x, y), maths::Vec2::new(70., 20.), 0.),
+        kind,
+    )
+}
+
+// The player is 30x30 with the default config
+pub fn player_at(x: f64, y: f64, velocity: (f64, f64)) -> Player {
+    let mut player = Player::new(&GameConfig::default());
+    player.rect.set_center(maths::Point::new(x, y));
+    player.velocity = maths::Vec2::new(velocity.0, velocity.1);
+    player
+}
+
+// Green platforms at those centers, with their index as id, and nothing else on the level
+// Default config: 540x960, the player starts at (270, 480)
+pub fn game_with(platforms: &[(f64, f64)]) -> Game {
+    let mut game = Game::with_seed(GameConfig::default(), 0);
+
+    game.platforms = platforms
+        .iter()
+        .enumerate()
+        .map(|(i, (x, y))| platform(i as u64, *x, *y, PlatformType::Green))
+        .collect();
+    game.enemies.clear();
+    game.power_ups.clear();
+
+    game
+}
@@ -0,0 +1,243 @@
+// Hand-authored levels, for regression tests and benchmarks that must not depend on the generator
+// Set GameConfig::track to play one instead of a generated level
+// Heights go up from the bottom of the first screen, x goes right from the left side of the screen
+use crate::{enemy::EnemyType, platform::PlatformType, power_up::PowerUpType};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Track {
+    #[serde(default)]
+    pub platforms: Vec<TrackPlatform>,
+    #[serde(default)]
+    pub enemies: Vec<TrackEnemy>,
+    #[serde(default)]
+    pub power_ups: Vec<TrackPowerUp>,
+    #[serde(default)]
+    pub end: TrackEnd,
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct TrackPlatform {
+    pub x: f64,
+    pub height: f64,
+    // Width and height, GameConfig's platform size if it's missing
+    #[serde(default)]
+    pub size: Option<[f64; 2]>,
+    pub kind: PlatformType,
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct TrackEnemy {
+    pub x: f64,
+    pub height: f64,
+    pub kind: EnemyType,
+}
+
+// Springs and trampolines aren't attached to anything, put them right on top of a platform
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct TrackPowerUp {
+    pub x: f64,
+    pub height: f64,
+    pub kind: PowerUpType,
+}
+
+// What happens once everything on the track has been placed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum TrackEnd {
+    // The generator continues from the highest platform
+    #[default]
+    Procedural,
+    // Players that get above the highest platform end their run with TerminalReason::TrackCompleted
+    Finish,
+}
+
+#[derive(Debug)]
+pub enum TrackError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl std::fmt::Display for TrackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Could not read the track file: {e}"),
+            Self::Json(e) => write!(f, "Could not parse the track: {e}"),
+            Self::Ron(e) => write!(f, "Could not parse the track: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for TrackError {}
+
+// Anything on the track, they're placed from the lowest one
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub(crate) enum TrackItem {
+    Platform(TrackPlatform),
+    Enemy(TrackEnemy),
+    PowerUp(TrackPowerUp),
+}
+
+impl TrackItem {
+    pub(crate) fn height(&self) -> f64 {
+        match self {
+            Self::Platform(platform) => platform.height,
+            Self::Enemy(enemy) => enemy.height,
+            Self::PowerUp(power_up) => power_up.height,
+        }
+    }
+}
+
+impl Track {
+    // .ron files are read as RON, anything else as JSON
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, TrackError> {
+        let path = path.as_ref();
+        let data = std::fs::read_to_string(path).map_err(TrackError::Io)?;
+
+        if path.extension().is_some_and(|extension| extension == "ron") {
+            ron::from_str(&data).map_err(TrackError::Ron)
+        } else {
+            serde_json::from_str(&data).map_err(TrackError::Json)
+        }
+    }
+
+    // The highest platform, the track ends there
+    pub fn top(&self) -> Option<&TrackPlatform> {
+        self.platforms
+            .iter()
+            .max_by(|a, b| a.height.total_cmp(&b.height))
+    }
+
+    // Highest first, so the next one to place can be popped
+    pub(crate) fn items(&self) -> Vec<TrackItem> {
+        let mut items = self
+            .platforms
+            .iter()
+            .copied()
+            .map(TrackItem::Platform)
+            .chain(self.enemies.iter().copied().map(TrackItem::Enemy))
+            .chain(self.power_ups.iter().copied().map(TrackItem::PowerUp))
+            .collect::<Vec<_>>();
+        items.sort_by(|a, b| b.height().total_cmp(&a.height()));
+
+        items
+    }
+}
+
+#[cfg(test)]
+mod tracks {
+    use super::*;
+    use crate::{Game, GameConfig, TerminalReason};
+
+    // A single platform right under the player's spawn
+    fn short_track(end: TrackEnd) -> Track {
+        Track {
+            platforms: vec![TrackPlatform {
+                x: 270.,
+                height: 100.,
+                size: None,
+                kind: PlatformType::Green,
+            }],
+            enemies: Vec::new(),
+            power_ups: Vec::new(),
+            end,
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let track: Track = ron::from_str(
+            "(
+                platforms: [
+                    (x: 270., height: 100., kind: Green),
+                    (x: 100., height: 250., kind: Blue),
+                ],
+                enemies: [(x: 400., height: 600., kind: Static)],
+                end: Finish,
+            )",
+        )
+        .unwrap();
+        assert_eq!(track.platforms.len(), 2);
+        assert_eq!(track.enemies.len(), 1);
+        assert_eq!(track.end, TrackEnd::Finish);
+        assert_eq!(track.top().unwrap().height, 250.);
+
+        let track: Track = serde_json::from_str(
+            r#"{
+                "platforms": [{ "x": 270, "height": 100, "size": [140, 20], "kind": "Red" }],
+                "power_ups": [{ "x": 270, "height": 120, "kind": "Spring" }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(track.platforms[0].size, Some([140., 20.]));
+        assert_eq!(track.power_ups.len(), 1);
+        assert_eq!(track.end, TrackEnd::Procedural);
+    }
+
+    #[test]
+    fn placed_from_the_bottom() {
+        let config = GameConfig {
+            track: Some(short_track(TrackEnd::Finish)),
+            ..Default::default()
+        };
+        let game = Game::with_seed(config, 0);
+
+        assert_eq!(game.platforms.len(), 1);
+        assert_eq!(game.platforms[0].rect.center().x, 270.);
+        assert_eq!(game.platforms[0].rect.center().y, 860.);
+    }
+
+    #[test]
+    fn finish() {
+        // A straight climb, each platform a jump above the previous one
+        let platforms = [300., 600., 900.]
+            .into_iter()
+            .map(|height| TrackPlatform {
+                x: 270.,
+                height,
+                size: None,
+                kind: PlatformType::Green,
+            })
+            .collect();
+        let config = GameConfig {
+            track: Some(Track {
+                platforms,
+                enemies: Vec::new(),
+                power_ups: Vec::new(),
+                end: TrackEnd::Finish,
+            }),
+            ..Default::default()
+        };
+        let mut game = Game::with_seed(config, 0);
+        let finish = game.finish_line().unwrap();
+
+        game.tick();
+        assert!(game.players[0].terminal.is_none());
+
+        while !game.is_over() && game.ticks < 500 {
+            game.tick();
+        }
+
+        let player = &game.players[0];
+        assert_eq!(player.terminal, Some(TerminalReason::TrackCompleted));
+        assert!(player.rect.center().y < finish);
+        assert!(player.stats.unique_platforms_landed >= 2);
+        // Nothing generated after the end, the lowest ones can be gone already
+        assert!(game.platforms.len() <= 3);
+    }
+
+    #[test]
+    fn procedural_after_the_end() {
+        let config = GameConfig {
+            track: Some(short_track(TrackEnd::Procedural)),
+            ..Default::default()
+        };
+        let mut game = Game::with_seed(config, 0);
+        game.tick();
+
+        assert!(game.players[0].terminal.is_none());
+        assert!(game
+            .platforms
+            .iter()
+            .any(|platform| platform.rect.center().y < 0.));
+    }
+}
@@ -1,92 +1,92 @@
 pub const NB_GAMES: usize = 3;
 pub const GAME_TIME_S: usize = 20; // Nb of secconds we let the ai play the game before registering their scrore
-pub const GAME_FPS: usize = 20; // 60
-pub const GAME_DELTA_TIME: f64 = 1. / GAME_FPS as f64;
+pub const GAME_FPS: usize = game::TICK_RATE as usize;
+pub const GAME_DELTA_TIME: f64 = game::TICK_DELTA_TIME;
+// Hard cap on a training run, GameConfig::termination can be set to never end one
+pub const MAX_GAME_TICKS: u64 = 30 * 60 * game::TICK_RATE as u64; // 30 minutes
 pub const NB_GENERATIONS: usize = 200;
 pub const NB_GENOME_PER_GEN: usize = 2_500;
 pub const MUTATION_RATE: f32 = 0.05;
 pub const MUTATION_PASSES: usize = 3;
+// Optional, the default game config is used if it's missing
+pub const GAME_CONFIG_PATH: &str = "./game_config.json";
+// If that file exists, its track is played instead of a generated level
+pub const TRACK_PATH: &str = "./track.ron";
 
 const NB_PLATFORM_IN: usize = 3;
 const OBJECT_DATA_LEN: usize = 2;
 // Player x + player y velocity + data for each platform we want to send
 pub const AGENT_IN: usize = 1 + 1 + 1 + NB_PLATFORM_IN * OBJECT_DATA_LEN;
-pub const AGENT_OUT: usize = 3; // None, Left, right
+pub const AGENT_OUT: usize = game::Action::COUNT; // One output per action
 
-pub fn generate_inputs(game: &game::Game) -> [f32; AGENT_IN] {
+// Observation of the player at that index
+pub fn generate_inputs(game: &game::Game, index: usize) -> [f32; AGENT_IN] {
     let mut inputs = Vec::new();
-
-    let build_single_input = |obj_rect: &maths::Rect, player: &maths::Rect| -> [f32; 2] {
-        let a = (
-            obj_rect.center().x,
-            maths::get_distance(obj_rect.center(), player.center()) as i32,
-        );
-
-        let b = (
-            obj_rect.center().x,
-            maths::get_distance(
-                obj_rect.center(),
-                maths::Point::new(player.center().x + game::GAME_WIDTH, player.center().y),
-            ) as i32,
-        );
-
-        let c = (
-            obj_rect.center().x,
-            maths::get_distance(
-                obj_rect.center(),
-                maths::Point::new(player.center().x - game::GAME_WIDTH, player.center().y),
-            ) as i32,
-        );
-
-        let min = [a, b, c].iter().cloned().min_by_key(|(_x, d)| *d).unwrap();
-
-        [min.0 as f32, min.1 as f32]
-    };
+    let player = &game.players[index];
+    let camera = &player.camera;
 
     // inputs.extend(rect_to_vec(&game.player.rect));
+    let position = camera.normalize(&game.config, player.rect.center());
     inputs.extend(
         [
             // game.player.rect.center().x as f32,
             // game.player.velocity.y as f32,
-            game.player.rect.center().x / game::GAME_WIDTH,
-            (game.player.rect.center().y - game.scroll as f64) / game::GAME_HEIGHT,
-            game.player.velocity.y,
+            position.x,
+            position.y,
+            player.velocity.y,
         ]
         .iter()
         .map(|v| *v as f32),
     );
 
-    // ordered by distance to player
-    inputs.extend({
-        let mut platform_data = game
-            .platforms
-            .iter()
+    // ordered by distance to player, zeros if there isn't enough platforms
+    inputs.extend(
+        game.nearest_platforms(index, NB_PLATFORM_IN)
+            .into_iter()
             .map(|platform| {
-                [
-                    platform.rect.center().x / game::GAME_WIDTH,
-                    (platform.rect.center().y - game.scroll as f64) / game::GAME_HEIGHT,
-                ]
+                let position = camera.normalize(&game.config, platform.rect.center());
+                [position.x, position.y]
             })
             // .map(rect_to_vec)
-            .collect::<Vec<_>>();
-
-        // platform_data.sort_unstable_by_key(|xd| {
-        //     // By default is low to high
-        //     xd[1] as i32
-        // });
-        let _end = platform_data.split_off(NB_PLATFORM_IN);
-
-        platform_data
-            .iter()
-            .cloned()
+            .chain(std::iter::repeat([0.; OBJECT_DATA_LEN]))
+            .take(NB_PLATFORM_IN)
             .flatten()
-            .map(|v| v as f32)
-            .collect::<Vec<f32>>()
-    });
+            .map(|v| v as f32),
+    );
 
     inputs.try_into().unwrap()
 }
 
+pub fn load_game_config() -> game::GameConfig {
+    let mut config = match std::fs::read_to_string(GAME_CONFIG_PATH) {
+        Ok(data) => match serde_json::from_str(&data) {
+            Ok(config) => config,
+            Err(e) => {
+                log::error!("Could not parse {GAME_CONFIG_PATH}: {e}\n\tFalling back to default");
+                game::GameConfig::default()
+            }
+        },
+        Err(_) => game::GameConfig::default(),
+    };
+
+    if std::path::Path::new(TRACK_PATH).exists() {
+        match game::track::Track::load(TRACK_PATH) {
+            Ok(track) => config.track = Some(track),
+            Err(e) => {
+                log::error!("Could not load {TRACK_PATH}: {e}\n\tFalling back to the generator")
+            }
+        }
+    }
+
+    config
+}
+
+// Levels played by every genome of that generation
+// They change every generation, so the population can't overfit a few levels
+pub fn game_seeds(generation: u64) -> [u64; NB_GAMES] {
+    std::array::from_fn(|i| generation * NB_GAMES as u64 + i as u64)
+}
+
 pub type Brain = neat::NeuralNetwork<AGENT_IN, AGENT_OUT>;
 
 pub struct PerformanceStats {
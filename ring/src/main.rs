@@ -5,8 +5,8 @@ use plotters::{
 };
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use ring::{
-    Brain, PerformanceStats, PlottingObserver, GAME_DELTA_TIME, GAME_FPS, GAME_TIME_S,
-    MUTATION_PASSES, MUTATION_RATE, NB_GAMES, NB_GENERATIONS, NB_GENOME_PER_GEN,
+    Brain, PerformanceStats, PlottingObserver, AGENT_IN, GAME_FPS, GAME_TIME_S, MUTATION_PASSES,
+    MUTATION_RATE, NB_GAMES, NB_GENERATIONS, NB_GENOME_PER_GEN,
 };
 use std::io::Write as _;
 
@@ -15,48 +15,42 @@ extern crate log;
 
 mod utils;
 
+static GAME_CONFIG: std::sync::OnceLock<game::GameConfig> = std::sync::OnceLock::new();
+// Generation being evaluated, it picks the levels of the fitness function
+static GENERATION: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
 fn fitness(brain: &Brain) -> f32 {
-    (0..NB_GAMES).map(|_| play_game(&brain)).sum::<f32>() / NB_GAMES as f32
+    // Every genome of a generation plays the same levels, so scores are comparable
+    ring::game_seeds(GENERATION.load(std::sync::atomic::Ordering::SeqCst))
+        .iter()
+        .map(|seed| play_game(brain, &mut new_env(*seed)).score)
+        .sum::<f32>()
+        / NB_GAMES as f32
 }
 
-fn play_game(brain: &Brain) -> f32 {
-    let mut game = game::Game::new();
-
-    let mut saved_score = game.score();
-    let mut save_timer = time::DTDelay::new(10.);
+fn new_env(seed: u64) -> game::Env<[f32; AGENT_IN]> {
+    let config = GAME_CONFIG.get_or_init(ring::load_game_config).clone();
+    game::Env::new(game::Game::with_seed(config, seed), |game| {
+        ring::generate_inputs(game, 0)
+    })
+}
 
-    // loop for the number of frames we want to play, should be enough frames to play 100s at 60fps
-    // for _ in 0..(GAME_FPS * GAME_TIME_S) {
-    while game.score() < 100_000. {
-        let output = brain.predict(ring::generate_inputs(&game));
+// The game ends the run itself, see GameConfig::mode and GameConfig::termination
+// MAX_GAME_TICKS stops it anyway if the config doesn't limit it, so a generation can't hang
+fn play_game(brain: &Brain, env: &mut game::Env<[f32; AGENT_IN]>) -> game::env::Info {
+    let mut observation = env.observe();
 
-        match output.iter().max_index().unwrap() {
-            0 => (), // No action
-            1 => game.player_move_left(),
-            2 => game.player_move_right(),
-            _ => (),
-        }
+    loop {
+        let output = brain.predict(observation);
 
-        game.update(GAME_DELTA_TIME);
-        save_timer.update(GAME_DELTA_TIME);
+        let step = env.step(game::Action::from_output(&output));
+        observation = step.observation;
 
-        if game.lost {
-            // println!("Lost: {}", game.score());
-            break;
-        }
-
-        if save_timer.ended() {
-            if game.score() == saved_score {
-                // The player stagnated and needs to be shot (ingame)
-                game.lost = true;
-                break;
-            }
-            saved_score = game.score();
-            save_timer.restart_custom_timeline(save_timer.time_since_ended());
+        if step.done || step.info.ticks >= ring::MAX_GAME_TICKS {
+            // println!("Lost: {}", step.info.score);
+            return step.info;
         }
     }
-
-    game.score()
 }
 
 fn sort_genomes(genomes: &[Brain]) -> Vec<(&Brain, f32)> {
@@ -83,6 +77,11 @@ fn main() {
 
     debug!("Starting training server");
 
+    debug!(
+        "Game config: {:?}",
+        GAME_CONFIG.get_or_init(ring::load_game_config)
+    );
+
     // unsafe {
     //     agent::LOADED_NNT = Some(
     //         serde_json::from_str::<neat::NNTSerde<{ AGENT_IN }, { AGENT_OUT }>>(include_str!(
@@ -136,6 +135,7 @@ fn main() {
         // debug!("Generation {}/{}", i + 1, NB_GENERATIONS,);
         // let t = std::time::Instant::now();
 
+        GENERATION.store(i as u64, std::sync::atomic::Ordering::SeqCst);
         sim.next_generation();
 
         // let (sorted_genome, sort_duration) = time::timeit(|| sort_genomes(&sim));
@@ -241,6 +241,27 @@ fn main() {
             .unwrap();
     }
 
+    // Record the best genome on its last levels so its runs can be watched in display
+    for seed in ring::game_seeds(GENERATION.load(std::sync::atomic::Ordering::SeqCst)) {
+        let mut env = new_env(seed).recording();
+        let info = play_game(genomes.first().unwrap().0, &mut env);
+        let replay = env.replay().unwrap();
+
+        if let Err(e) = replay.verify() {
+            error!("Replay of seed {seed} doesn't reproduce its run: {e}");
+        }
+
+        match replay.save(format!("./sim/best_seed{seed}.replay.json")) {
+            Ok(()) => debug!(
+                "Saved replay of seed {seed}, score: {}, ended by: {:?}\n{:?}",
+                info.score,
+                info.terminal,
+                env.game().players[0].stats
+            ),
+            Err(e) => error!("Could not save replay of seed {seed}: {e}"),
+        }
+    }
+
     drop(sim);
 
     let data: Vec<_> = std::sync::Arc::into_inner(performance_stats)